@@ -0,0 +1,174 @@
+use std::env;
+use std::io::{self, BufRead, Write};
+
+use deck::CardCounting;
+use deck::blackjack::{
+    Action, BasicStrategy, Decision, Outcome, RoundResult, Rules, Strategy, Table,
+};
+
+const STARTING_BALANCE: i64 = 1000;
+
+/// Asks the player at the terminal for every decision
+struct TerminalPlayer {
+    input: io::StdinLock<'static>,
+}
+
+impl TerminalPlayer {
+    fn prompt(&mut self, question: &str) -> Option<String> {
+        print!("{}", question);
+        io::stdout().flush().ok()?;
+        let mut line = String::new();
+        match self.input.read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim().to_lowercase()),
+        }
+    }
+}
+
+impl Strategy for TerminalPlayer {
+    fn decide(&mut self, decision: &Decision) -> Action {
+        let cards: Vec<String> = decision.cards.iter().map(|card| card.to_string()).collect();
+        println!(
+            "Hand {}/{}: {} ({}) - dealer shows {}",
+            decision.hand_index + 1,
+            decision.hand_count,
+            cards.join(", "),
+            decision.total,
+            decision.dealer_up
+        );
+
+        let mut options = vec!["[h]it", "[s]tand"];
+        if decision.can_double {
+            options.push("[d]ouble");
+        }
+        if decision.can_split {
+            options.push("s[p]lit");
+        }
+        if decision.can_surrender {
+            options.push("su[r]render");
+        }
+        let question = format!("{}? ", options.join(", "));
+
+        loop {
+            let action = match self.prompt(&question).as_deref() {
+                // End of input: stop drawing cards
+                None => return Action::Stand,
+                Some("h" | "hit") => Action::Hit,
+                Some("s" | "stand") => Action::Stand,
+                Some("d" | "double") => Action::Double,
+                Some("p" | "split") => Action::Split,
+                Some("r" | "surrender") => Action::Surrender,
+                Some(_) => continue,
+            };
+            if decision.is_allowed(action) {
+                return action;
+            }
+            println!("You can't {} now", action);
+        }
+    }
+
+    fn take_insurance(&mut self, _decision: &Decision) -> bool {
+        matches!(
+            self.prompt("Dealer shows an Ace. Insurance? [y/N] ")
+                .as_deref(),
+            Some("y" | "yes")
+        )
+    }
+}
+
+fn print_result(result: &RoundResult) {
+    println!("Dealer has {}", result.dealer);
+    for hand in &result.hands {
+        let outcome = match hand.outcome {
+            Outcome::Blackjack => "Blackjack!",
+            Outcome::Win => "You win",
+            Outcome::Push => "Push",
+            Outcome::Lose => "You lose",
+            Outcome::Bust => "Bust",
+            Outcome::Surrender => "Surrendered",
+        };
+        println!("{}: {} ({:+})", hand.cards, outcome, hand.net);
+    }
+    if result.insurance_net != 0 {
+        println!("Insurance: {:+}", result.insurance_net);
+    }
+}
+
+fn play() {
+    let mut table = Table::new(Rules::default(), STARTING_BALANCE);
+    let mut player = TerminalPlayer {
+        input: io::stdin().lock(),
+    };
+
+    loop {
+        let shoe = table.shoe();
+        let question = format!(
+            "Balance: {} (count {:+}, true {:+.1}). Bet (empty to quit)? ",
+            table.bankroll.balance,
+            shoe.running_count(),
+            shoe.true_count()
+        );
+        let bet = match player.prompt(&question) {
+            None => break,
+            Some(line) if line.is_empty() => break,
+            Some(line) => match line.parse::<i64>() {
+                Ok(bet) => bet,
+                Err(_) => continue,
+            },
+        };
+
+        match table.play_round(bet, &mut player) {
+            Ok(result) => print_result(&result),
+            Err(err) => println!("{}", err),
+        }
+        if table.bankroll.balance <= 0 {
+            println!("You're out of chips!");
+            break;
+        }
+    }
+
+    println!(
+        "Finished with {} after {} rounds",
+        table.bankroll.balance, table.bankroll.rounds
+    );
+}
+
+// Plays `rounds` hands of basic strategy with a flat bet and reports the edge
+fn simulate(rounds: u64, seed: u64) {
+    let bet = 10;
+    let mut table = Table::with_seed(Rules::default(), i64::MAX / 2, seed);
+    let mut strategy = BasicStrategy;
+
+    for _ in 0..rounds {
+        if let Err(err) = table.play_round(bet, &mut strategy) {
+            println!("{}", err);
+            return;
+        }
+    }
+
+    let bankroll = &table.bankroll;
+    println!(
+        "{} rounds, wagered {}, net {:+} ({:+.3}% of action)",
+        bankroll.rounds,
+        bankroll.wagered,
+        bankroll.net,
+        100.0 * bankroll.net as f64 / bankroll.wagered as f64
+    );
+}
+
+fn main() {
+    // blackjack                        -> play at the terminal
+    // blackjack simulate <rounds> [seed] -> basic strategy simulation
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("simulate") => {
+            let rounds = args
+                .get(1)
+                .and_then(|arg| arg.parse().ok())
+                .unwrap_or(100_000);
+            let seed = args.get(2).and_then(|arg| arg.parse().ok()).unwrap_or(0);
+            simulate(rounds, seed);
+        }
+        _ => play(),
+    }
+}
@@ -0,0 +1,189 @@
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::process;
+use std::thread;
+
+use deck::eights::can_play;
+use deck::net::{Client, ClientMessage, GameEnd, Server, ServerMessage};
+use deck::{Card, CardFormat, Hand, Rank, Suit};
+
+// eights serve [addr] [seed]   -> host a game for two players
+// eights join <addr> <name>    -> play at the terminal
+// eights demo [seed]           -> two bots play each other over localhost
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("serve") => serve(
+            args.get(1).map_or("127.0.0.1:7878", String::as_str),
+            args.get(2).and_then(|arg| arg.parse().ok()).unwrap_or(0),
+        ),
+        Some("join") if args.len() == 3 => join(&args[1], &args[2]),
+        Some("demo") => demo(args.get(1).and_then(|arg| arg.parse().ok()).unwrap_or(0)),
+        _ => {
+            eprintln!("usage: eights serve [addr] [seed] | join <addr> <name> | demo [seed]");
+            process::exit(2);
+        }
+    };
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+fn serve(addr: &str, seed: u64) -> io::Result<()> {
+    let server = Server::bind(addr)?;
+    println!("Waiting for two players on {}", server.local_addr()?);
+    report(server.host_game(seed)?);
+    Ok(())
+}
+
+fn demo(seed: u64) -> io::Result<()> {
+    let server = Server::bind("127.0.0.1:0")?;
+    let addr = server.local_addr()?;
+    let bots: Vec<_> = ["North", "South"]
+        .into_iter()
+        .map(|name| thread::spawn(move || play_bot(addr, name)))
+        .collect();
+
+    let end = server.host_game(seed)?;
+    for bot in bots {
+        bot.join().expect("bot panicked")?;
+    }
+    report(end);
+    Ok(())
+}
+
+fn report(end: GameEnd) {
+    match end {
+        GameEnd::Winner(seat) => println!("Seat {} wins", seat),
+        GameEnd::Stalemate => println!("Nobody can move, the game is a draw"),
+        GameEnd::Abandoned(seat) => println!("Seat {} left, game abandoned", seat),
+    }
+}
+
+/// Plays the first legal card, calling its most held suit on an Eight
+fn play_bot(addr: std::net::SocketAddr, name: &str) -> io::Result<()> {
+    let mut client = Client::connect(addr, name)?;
+    let mut hand = Hand::new();
+    loop {
+        match client.recv()? {
+            ServerMessage::Hand(cards) => hand = cards,
+            ServerMessage::State(state) if state.turn == client.seat() => {
+                let choice = hand
+                    .iter()
+                    .find(|card| can_play(card, &state.top, state.suit))
+                    .copied();
+                let message = match choice {
+                    Some(card) if card.rank == Rank::Eight => {
+                        ClientMessage::Play(card, Some(favourite_suit(&hand)))
+                    }
+                    Some(card) => ClientMessage::Play(card, None),
+                    None => ClientMessage::Draw,
+                };
+                println!("{} ({}): {}", name, hand.format(CardFormat::Short), message);
+                client.send(&message)?;
+            }
+            ServerMessage::GameOver { .. } | ServerMessage::Left { .. } => return Ok(()),
+            _ => {}
+        }
+    }
+}
+
+fn favourite_suit(hand: &Hand) -> Suit {
+    let groups = hand.group_by_suit();
+    Suit::ALL
+        .into_iter()
+        .max_by_key(|suit| groups.get(suit).map_or(0, Vec::len))
+        .expect("there are four suits")
+}
+
+fn join(addr: &str, name: &str) -> io::Result<()> {
+    println!("Connecting to {}...", addr);
+    let mut client = Client::connect(addr, name)?;
+    let names = client.names().to_vec();
+    let me = client.seat();
+    println!("Seated as {} against {}", me, names.join(" / "));
+
+    let mut hand = Hand::new();
+    let stdin = io::stdin();
+    let mut input = stdin.lock();
+    loop {
+        let message = client.recv()?;
+        let ask = match &message {
+            ServerMessage::Hand(cards) => {
+                hand = cards.clone();
+                false
+            }
+            ServerMessage::State(state) => {
+                println!(
+                    "Pile: {} (follow {})  stock {}  cards {:?}",
+                    state.top.short(),
+                    state.suit,
+                    state.stock,
+                    state.hand_sizes
+                );
+                state.turn == me
+            }
+            ServerMessage::Played { seat, card, suit } => {
+                let called = suit.map_or(String::new(), |suit| format!(", calls {}", suit));
+                println!("{} plays {}{}", names[*seat], card, called);
+                false
+            }
+            ServerMessage::Drew { seat, passed } => {
+                let verb = if *passed { "passes" } else { "draws" };
+                println!("{} {}", names[*seat], verb);
+                false
+            }
+            ServerMessage::Rejected(reason) => {
+                println!("Refused: {}", reason);
+                true
+            }
+            ServerMessage::GameOver { winner } => {
+                match winner {
+                    Some(seat) if *seat == me => println!("You win!"),
+                    Some(seat) => println!("{} wins", names[*seat]),
+                    None => println!("Draw"),
+                }
+                return Ok(());
+            }
+            ServerMessage::Left { seat } => {
+                println!("{} left the game", names[*seat]);
+                return Ok(());
+            }
+            ServerMessage::Welcome { .. } => false,
+        };
+        if ask {
+            let Some(choice) = prompt(&mut input, &hand)? else {
+                return Ok(());
+            };
+            client.send(&choice)?;
+        }
+    }
+}
+
+/// Reads "8h s", "7c" or "d" (draw) until it makes sense. `None` at end of
+/// input.
+fn prompt(input: &mut impl BufRead, hand: &Hand) -> io::Result<Option<ClientMessage>> {
+    loop {
+        print!(
+            "Your hand: {}\nPlay a card (and a suit for an Eight) or [d]raw: ",
+            hand.format(CardFormat::Short)
+        );
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim();
+        if line == "d" || line == "draw" {
+            return Ok(Some(ClientMessage::Draw));
+        }
+        let mut words = line.split_whitespace();
+        let card: Option<Card> = words.next().and_then(|word| word.parse().ok());
+        let suit: Option<Suit> = words.next().and_then(|word| word.parse().ok());
+        match card {
+            Some(card) => return Ok(Some(ClientMessage::Play(card, suit))),
+            None => println!("Type a card like 7c, or 8h s to play an Eight and call Spades"),
+        }
+    }
+}
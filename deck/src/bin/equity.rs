@@ -0,0 +1,101 @@
+use std::env;
+use std::process;
+use std::time::Instant;
+
+use deck::notation::parse_cards;
+use deck::{EquityCalculator, Hand};
+
+// equity <hole cards>... [--board <cards>] [--samples N] [--exact-limit N]
+//        [--seed S] [--threads T]
+//
+// e.g. `equity AsAh KsKh` or `equity AhKh QsQd 7c7d --board "Qh Jh 2c"`
+fn main() {
+    let mut players = vec![];
+    let mut board = vec![];
+    let mut samples = None;
+    let mut exact_limit = None;
+    let mut seed = None;
+    let mut threads = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .unwrap_or_else(|| fail(&format!("{} needs a value", name)))
+        };
+        match arg.as_str() {
+            "--board" => board = parse_or_fail(&value("--board")),
+            "--samples" => samples = Some(number_or_fail(&value("--samples"))),
+            "--exact-limit" => exact_limit = Some(number_or_fail(&value("--exact-limit"))),
+            "--seed" => seed = Some(number_or_fail(&value("--seed"))),
+            "--threads" => threads = Some(number_or_fail(&value("--threads")) as usize),
+            hole => players.push(Hand::from(parse_or_fail(&spaced(hole)))),
+        }
+    }
+
+    let mut calculator = EquityCalculator::new(players.clone()).with_board(&board);
+    if let Some(samples) = samples {
+        calculator = calculator.with_samples(samples);
+    }
+    if let Some(limit) = exact_limit {
+        calculator = calculator.with_exact_limit(limit);
+    }
+    if let Some(seed) = seed {
+        calculator = calculator.with_seed(seed);
+    }
+    if let Some(threads) = threads {
+        calculator = calculator.with_threads(threads);
+    }
+
+    let start = Instant::now();
+    let report = calculator
+        .calculate()
+        .unwrap_or_else(|err| fail(&err.to_string()));
+    for (hand, line) in players.iter().zip(report.to_string().lines()) {
+        println!("{:<8}{}", hand.format(deck::CardFormat::Short), line);
+    }
+    println!(
+        "{} {} boards in {:.2?}",
+        if report.exact {
+            "Enumerated"
+        } else {
+            "Sampled"
+        },
+        report.boards,
+        start.elapsed()
+    );
+}
+
+/// "AhKh" -> "Ah Kh", so hole cards can be typed without a space
+fn spaced(hole: &str) -> String {
+    if hole.contains(' ') || hole.contains(',') {
+        return hole.to_string();
+    }
+    // The first card ends with its suit
+    match hole
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| "cdhs♣♦♥♠".contains(*c))
+    {
+        Some((i, suit)) => {
+            let split = i + suit.len_utf8();
+            format!("{} {}", &hole[..split], &hole[split..])
+        }
+        None => hole.to_string(),
+    }
+}
+
+fn parse_or_fail(cards: &str) -> Vec<deck::Card> {
+    parse_cards(cards).unwrap_or_else(|err| fail(&format!("{}: {}", cards, err)))
+}
+
+fn number_or_fail(value: &str) -> u64 {
+    value
+        .parse()
+        .unwrap_or_else(|_| fail(&format!("not a number: {}", value)))
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(2);
+}
@@ -0,0 +1,43 @@
+use std::env;
+
+use deck::klondike::{DrawMode, Klondike, SolveOutcome};
+
+// klondike [seed] [1|3] [budget]
+//
+// Deals a seeded game, shows it and asks the solver whether it can be won.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let seed = args.first().and_then(|arg| arg.parse().ok()).unwrap_or(0);
+    let mode = match args.get(1).map(String::as_str) {
+        Some("3") => DrawMode::Three,
+        _ => DrawMode::One,
+    };
+    let budget = args
+        .get(2)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(100_000);
+
+    let game = Klondike::deal(seed, mode);
+    println!("Deal #{} ({:?})\n{}", seed, mode, game);
+
+    match game.solve(budget) {
+        SolveOutcome::Solved(line) => {
+            println!("Winnable in {} moves:", line.len());
+            for (i, mv) in line.iter().enumerate() {
+                println!("{:>4}. {}", i + 1, mv);
+            }
+        }
+        SolveOutcome::Unwinnable { explored } => {
+            println!("Unwinnable ({} positions explored)", explored)
+        }
+        SolveOutcome::Inconclusive { explored } => {
+            println!(
+                "No win found in {} positions, but some moves were skipped",
+                explored
+            )
+        }
+        SolveOutcome::BudgetExhausted { explored } => {
+            println!("Gave up after {} positions", explored)
+        }
+    }
+}
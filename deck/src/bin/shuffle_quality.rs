@@ -0,0 +1,43 @@
+use std::env;
+
+use deck::shuffle::ShuffleSequence;
+use deck::shuffle::quality::measure;
+
+// shuffle_quality [steps] [trials] [seed]
+//
+// Prints how random a 52-card deck looks after 1 to 10 repetitions of a
+// shuffling routine, e.g. `shuffle_quality riffle` shows why seven riffles
+// are usually called enough.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let routine: ShuffleSequence = match args.first().map(|arg| arg.parse()) {
+        None => ShuffleSequence::from(vec![deck::shuffle::ShuffleStep::Riffle]),
+        Some(Ok(routine)) => routine,
+        Some(Err(err)) => {
+            eprintln!("{}", err);
+            std::process::exit(2);
+        }
+    };
+    let trials = args
+        .get(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(10_000);
+    let seed = args.get(2).and_then(|arg| arg.parse().ok()).unwrap_or(0);
+
+    println!("Routine: {}, {} trials per row", routine, trials);
+    println!(
+        "{:>6} {:>16} {:>14} {:>10}",
+        "times", "rising seqs", "chi-square", "p-value"
+    );
+    for times in 1..=10 {
+        let report = measure(&routine.clone().repeat(times), 52, trials, seed);
+        println!(
+            "{:>6} {:>7.2} / {:>6.1} {:>14.1} {:>10.4}",
+            times,
+            report.mean_rising_sequences,
+            report.expected_rising_sequences,
+            report.position_bias.statistic,
+            report.position_bias.p_value
+        );
+    }
+}
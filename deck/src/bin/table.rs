@@ -0,0 +1,426 @@
+use std::env;
+use std::io::{self, BufRead, Write};
+
+use deck::blackjack::{Action, Decision, Outcome, Rules, Strategy, Table};
+use deck::eights::{CrazyEights, Drawn, can_play};
+use deck::klondike::{COLUMNS, DrawMode, Klondike, Move, SolveOutcome};
+use deck::render::{Block, Color, FAN_COLUMNS, Style, clear_screen};
+use deck::shuffle::random_seed;
+use deck::{Card, Hand, Rank, Suit};
+
+// table [blackjack|klondike|eights] [seed]
+//
+// Plays the built-in games with cards drawn as boxes. Colors and Unicode are
+// used when the terminal supports them (set NO_COLOR to turn color off).
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let style = Style::detect();
+    let mut screen = Screen {
+        style,
+        input: io::stdin().lock(),
+    };
+    let seed = args
+        .get(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or_else(random_seed);
+
+    let game = match args.first() {
+        Some(game) => game.clone(),
+        None => match screen.ask("Play [b]lackjack, [k]londike or crazy [e]ights? ") {
+            Some(answer) => answer,
+            None => return,
+        },
+    };
+    match game.as_str() {
+        "b" | "blackjack" => blackjack(&mut screen, seed),
+        "k" | "klondike" => klondike(&mut screen, seed),
+        "e" | "eights" => eights(&mut screen, seed),
+        other => eprintln!("Unknown game '{}'", other),
+    }
+}
+
+/// The terminal: draws blocks and reads answers
+struct Screen {
+    style: Style,
+    input: io::StdinLock<'static>,
+}
+
+impl Screen {
+    fn show(&self, block: &Block) {
+        print!("{}{}", clear_screen(&self.style), block.render(&self.style));
+    }
+
+    /// `None` at end of input
+    fn ask(&mut self, question: &str) -> Option<String> {
+        print!("{}", question);
+        io::stdout().flush().ok()?;
+        let mut line = String::new();
+        match self.input.read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim().to_lowercase()),
+        }
+    }
+
+    fn cards(&self, cards: &[Card]) -> Block {
+        if cards.is_empty() {
+            return Block::empty_slot(&self.style);
+        }
+        Block::hand(cards, &self.style)
+    }
+
+    fn labelled(&self, label: &str, block: Block) -> Block {
+        Block::above(&[Block::colored(label, Color::Yellow), block])
+    }
+}
+
+// Blackjack
+
+struct TablePlayer<'a> {
+    screen: &'a mut Screen,
+}
+
+impl Strategy for TablePlayer<'_> {
+    fn decide(&mut self, decision: &Decision) -> Action {
+        let screen = &mut *self.screen;
+        let style = screen.style;
+        let dealer = Block::beside(
+            &[
+                Block::card(&decision.dealer_up, &style),
+                Block::face_down(&style),
+            ],
+            1,
+        );
+        let player = screen.cards(decision.cards);
+        let title = format!(
+            "Your hand {}/{} ({}), bet {}",
+            decision.hand_index + 1,
+            decision.hand_count,
+            decision.total,
+            decision.bet
+        );
+        screen.show(&Block::above(&[
+            screen.labelled("Dealer", dealer),
+            Block::text(" "),
+            screen.labelled(&title, player),
+        ]));
+
+        let mut options = vec!["[h]it", "[s]tand"];
+        if decision.can_double {
+            options.push("[d]ouble");
+        }
+        if decision.can_split {
+            options.push("s[p]lit");
+        }
+        if decision.can_surrender {
+            options.push("su[r]render");
+        }
+        let question = format!("{}? ", options.join(", "));
+        loop {
+            let action = match screen.ask(&question).as_deref() {
+                None => return Action::Stand,
+                Some("h") => Action::Hit,
+                Some("s") => Action::Stand,
+                Some("d") => Action::Double,
+                Some("p") => Action::Split,
+                Some("r") => Action::Surrender,
+                Some(_) => continue,
+            };
+            if decision.is_allowed(action) {
+                return action;
+            }
+        }
+    }
+
+    fn take_insurance(&mut self, _decision: &Decision) -> bool {
+        matches!(
+            self.screen
+                .ask("Dealer shows an Ace. Insurance? [y/N] ")
+                .as_deref(),
+            Some("y")
+        )
+    }
+}
+
+fn blackjack(screen: &mut Screen, seed: u64) {
+    let mut table = Table::with_seed(Rules::default(), 1000, seed);
+    loop {
+        let question = format!("Balance {}. Bet (empty to quit)? ", table.bankroll.balance);
+        let bet = match screen.ask(&question) {
+            None => break,
+            Some(line) if line.is_empty() => break,
+            Some(line) => match line.parse::<i64>() {
+                Ok(bet) => bet,
+                Err(_) => {
+                    println!("{} isn't a number of chips", line);
+                    continue;
+                }
+            },
+        };
+        let result = match table.play_round(bet, &mut TablePlayer { screen }) {
+            Ok(result) => result,
+            Err(err) => {
+                println!("{}", err);
+                continue;
+            }
+        };
+
+        let mut rows = vec![screen.labelled("Dealer", screen.cards(result.dealer.cards()))];
+        for hand in &result.hands {
+            let (text, color) = match hand.outcome {
+                Outcome::Blackjack => ("Blackjack!", Color::Green),
+                Outcome::Win => ("You win", Color::Green),
+                Outcome::Push => ("Push", Color::Yellow),
+                Outcome::Lose => ("You lose", Color::Red),
+                Outcome::Bust => ("Bust", Color::Red),
+                Outcome::Surrender => ("Surrendered", Color::Red),
+            };
+            rows.push(Block::text(" "));
+            rows.push(Block::beside(
+                &[
+                    screen.cards(hand.cards.cards()),
+                    Block::colored(&format!("\n\n{} ({:+})", text, hand.net), color),
+                ],
+                2,
+            ));
+        }
+        screen.show(&Block::above(&rows));
+        if table.bankroll.balance <= 0 {
+            println!("You're out of chips!");
+            break;
+        }
+    }
+}
+
+// Klondike
+
+fn klondike_board(game: &Klondike, style: &Style) -> Block {
+    let stock = if game.stock_len() > 0 {
+        Block::face_down(style)
+    } else {
+        Block::empty_slot(style)
+    };
+    let waste = match game.waste_top() {
+        Some(card) => Block::card(card, style),
+        None => Block::empty_slot(style),
+    };
+    let mut top = vec![stock, waste, Block::text(&" ".repeat(7))];
+    for suit in Suit::ALL {
+        top.push(match game.foundation(suit).last() {
+            Some(card) => Block::card(card, style),
+            None => Block::empty_slot(style),
+        });
+    }
+
+    let columns: Vec<Block> = game
+        .tableau()
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let mut cards: Vec<Block> = column
+                .face_down
+                .iter()
+                .map(|_| Block::face_down(style))
+                .collect();
+            cards.extend(column.face_up.iter().map(|card| Block::card(card, style)));
+            if cards.is_empty() {
+                cards.push(Block::empty_slot(style));
+            }
+            Block::above(&[
+                Block::text(&format!("   {}", i + 1)),
+                Block::fanned_down(&cards, 2),
+            ])
+        })
+        .collect();
+
+    Block::above(&[
+        Block::text(&format!(" stock {:<2}  waste", game.stock_len())),
+        Block::beside(&top, 1),
+        Block::text(" "),
+        Block::beside(&columns, 1),
+    ])
+}
+
+/// "d", "wf", "w3", "3f", "35", "fh3"
+fn parse_klondike(game: &Klondike, command: &str) -> Option<Move> {
+    let column = |c: char| {
+        c.to_digit(10)
+            .map(|n| n as usize)
+            .filter(|n| (1..=COLUMNS).contains(n))
+            .map(|n| n - 1)
+    };
+    let chars: Vec<char> = command.chars().collect();
+    match chars.as_slice() {
+        ['d'] => Some(Move::Draw),
+        ['w', 'f'] => Some(Move::WasteToFoundation),
+        ['w', to] => Some(Move::WasteToTableau(column(*to)?)),
+        [from, 'f'] => Some(Move::TableauToFoundation(column(*from)?)),
+        ['f', suit, to] => Some(Move::FoundationToTableau {
+            suit: suit.to_string().parse().ok()?,
+            to: column(*to)?,
+        }),
+        [from, to] => {
+            let (from, to) = (column(*from)?, column(*to)?);
+            // Only one run can fit on the target, find how long it is
+            game.legal_moves().into_iter().find(|mv| {
+                matches!(mv, Move::TableauToTableau { from: f, to: t, .. } if *f == from && *t == to)
+            })
+        }
+        _ => None,
+    }
+}
+
+fn klondike(screen: &mut Screen, seed: u64) {
+    let mut game = Klondike::deal(seed, DrawMode::One);
+    let mut message = format!("Deal #{}", seed);
+    while !game.is_won() {
+        screen.show(&Block::above(&[
+            klondike_board(&game, &screen.style),
+            Block::text(" "),
+            Block::text(&message),
+        ]));
+        let question = "d draw, wf/w3 waste, 3f/35 column, fh3 back down, u undo, ? hint, q quit: ";
+        let Some(command) = screen.ask(question) else {
+            return;
+        };
+        message = match command.as_str() {
+            "q" => return,
+            "u" => match game.undo() {
+                Ok(()) => String::from("Undone"),
+                Err(err) => err.to_string(),
+            },
+            "?" => match game.solve(50_000) {
+                SolveOutcome::Solved(line) => format!("Try: {}", line[0]),
+                SolveOutcome::Unwinnable { .. } => String::from("No way to win from here"),
+                SolveOutcome::Inconclusive { .. } | SolveOutcome::BudgetExhausted { .. } => {
+                    String::from("No hint found")
+                }
+            },
+            command => match parse_klondike(&game, command) {
+                Some(mv) => match game.apply(mv) {
+                    Ok(()) => mv.to_string(),
+                    Err(err) => err.to_string(),
+                },
+                None => format!("Don't know '{}'", command),
+            },
+        };
+    }
+    screen.show(&klondike_board(&game, &screen.style));
+    println!("Solved in {} moves!", game.moves_made());
+}
+
+// Crazy Eights against the computer
+
+fn eights(screen: &mut Screen, seed: u64) {
+    const YOU: usize = 0;
+    const COMPUTER: usize = 1;
+    let mut game = CrazyEights::deal(2, seed).expect("two players can always be dealt");
+    let mut message = String::from("Match the suit or rank, Eights are wild");
+
+    while !game.is_over() {
+        if game.turn() == COMPUTER {
+            message = computer_turn(&mut game, COMPUTER);
+            continue;
+        }
+
+        let style = screen.style;
+        let backs: Vec<Block> = game
+            .hand(COMPUTER)
+            .iter()
+            .map(|_| Block::face_down(&style))
+            .collect();
+        let hand = game.hand(YOU);
+        let numbers: String = (1..=hand.len())
+            .map(|i| format!("{:<width$}", i, width = FAN_COLUMNS))
+            .collect::<String>();
+        let pile = Block::beside(
+            &[
+                Block::face_down(&style),
+                Block::card(&game.top(), &style),
+                Block::text(&format!(
+                    "\n{} in stock\n\nFollow {}",
+                    game.stock_len(),
+                    game.suit()
+                )),
+            ],
+            1,
+        );
+        screen.show(&Block::above(&[
+            screen.labelled("Computer", Block::fanned_right(&backs, FAN_COLUMNS)),
+            Block::text(" "),
+            pile,
+            Block::text(" "),
+            screen.labelled("You", screen.cards(hand.cards())),
+            Block::text(&numbers),
+            Block::text(" "),
+            Block::text(&message),
+        ]));
+
+        let Some(answer) = screen.ask("Card number to play, or [d]raw: ") else {
+            return;
+        };
+        message = if answer == "d" {
+            match game.draw(YOU) {
+                Ok(Drawn::Card(card)) => format!("You drew the {}", card),
+                Ok(Drawn::Nothing) => String::from("Nothing left to draw, you pass"),
+                Err(err) => err.to_string(),
+            }
+        } else {
+            let Some(card) = answer
+                .parse::<usize>()
+                .ok()
+                .and_then(|n| n.checked_sub(1))
+                .and_then(|i| game.hand(YOU).cards().get(i).copied())
+            else {
+                message = format!("No card '{}'", answer);
+                continue;
+            };
+            let called = if card.rank == Rank::Eight {
+                screen
+                    .ask("Call a suit [c/d/h/s]: ")
+                    .and_then(|suit| suit.parse().ok())
+            } else {
+                None
+            };
+            match game.play(YOU, card, called) {
+                Ok(()) => format!("You played the {}", card),
+                Err(err) => err.to_string(),
+            }
+        };
+    }
+
+    match game.winner() {
+        Some(YOU) => println!("You win!"),
+        Some(_) => println!("The computer wins"),
+        None => println!("Nobody can move, it's a draw"),
+    }
+}
+
+/// Plays the first card that fits, calling its longest suit on an Eight
+fn computer_turn(game: &mut CrazyEights, seat: usize) -> String {
+    let hand: Hand = game.hand(seat).clone();
+    let playable = hand
+        .iter()
+        .find(|card| can_play(card, &game.top(), game.suit()))
+        .copied();
+    match playable {
+        Some(card) => {
+            let called = (card.rank == Rank::Eight).then(|| {
+                let groups = hand.group_by_suit();
+                Suit::ALL
+                    .into_iter()
+                    .max_by_key(|suit| groups.get(suit).map_or(0, Vec::len))
+                    .expect("there are four suits")
+            });
+            game.play(seat, card, called)
+                .expect("the computer only plays legal cards");
+            match called {
+                Some(suit) => format!("Computer played the {} and called {}", card, suit),
+                None => format!("Computer played the {}", card),
+            }
+        }
+        None => match game.draw(seat) {
+            Ok(Drawn::Card(_)) => String::from("Computer drew a card"),
+            _ => String::from("Computer passed"),
+        },
+    }
+}
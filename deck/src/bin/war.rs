@@ -0,0 +1,35 @@
+use std::env;
+
+use deck::war::{Pickup, ShortHanded, TieRule, WarRules, simulate};
+
+// war [games] [seed] [--down N] [--split] [--short-loses] [--shuffle-pickup]
+//
+// Plays many seeded games of War and prints how long they lasted.
+fn main() {
+    let mut rules = WarRules::default();
+    let mut numbers = vec![];
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--down" => {
+                let face_down = args.next().and_then(|n| n.parse().ok()).unwrap_or(3);
+                rules.tie = TieRule::War { face_down };
+            }
+            "--split" => rules.tie = TieRule::Split,
+            "--short-loses" => rules.short_handed = ShortHanded::Loses,
+            "--shuffle-pickup" => rules.pickup = Pickup::Shuffled,
+            _ => match arg.parse::<u64>() {
+                Ok(n) => numbers.push(n),
+                Err(_) => {
+                    eprintln!("unknown argument '{}'", arg);
+                    std::process::exit(2);
+                }
+            },
+        }
+    }
+    let games = numbers.first().copied().unwrap_or(10_000) as usize;
+    let seed = numbers.get(1).copied().unwrap_or(0);
+
+    println!("{:?}", rules);
+    print!("{}", simulate(rules, games, seed));
+}
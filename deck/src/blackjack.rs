@@ -0,0 +1,820 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::card::{Card, Rank};
+use crate::hand::Hand;
+use crate::shoe::Shoe;
+use crate::shuffle::random_seed;
+use crate::source::CardSource;
+
+/// Whether the dealer draws on a soft 17
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DealerRule {
+    /// S17: the dealer stands on every 17
+    StandSoft17,
+    /// H17: the dealer hits a soft 17 (Ace counted as 11)
+    HitSoft17,
+}
+
+/// A payout ratio, e.g. 3:2 pays 15 on a 10 bet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Payout {
+    pub win: i64,
+    pub per: i64,
+}
+
+impl Payout {
+    pub const THREE_TO_TWO: Payout = Payout { win: 3, per: 2 };
+    pub const SIX_TO_FIVE: Payout = Payout { win: 6, per: 5 };
+    pub const EVEN: Payout = Payout { win: 1, per: 1 };
+
+    /// Winnings on `bet`, rounded down to a whole chip
+    pub fn on(&self, bet: i64) -> i64 {
+        bet * self.win / self.per
+    }
+}
+
+/// Table rules. `Rules::default()` is a common Las Vegas Strip game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rules {
+    /// Number of decks in the shoe
+    pub decks: usize,
+    /// Share of the shoe dealt before the cut card comes out
+    pub penetration: f64,
+    pub dealer: DealerRule,
+    pub blackjack_payout: Payout,
+    pub insurance_payout: Payout,
+    /// Offer insurance when the dealer shows an Ace
+    pub insurance: bool,
+    /// Late surrender: give up half the bet on the first two cards
+    pub surrender: bool,
+    pub double_after_split: bool,
+    /// Most hands a player can hold after splitting
+    pub max_split_hands: usize,
+    pub resplit_aces: bool,
+    /// Allow more cards on hands made by splitting Aces
+    pub hit_split_aces: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            decks: 6,
+            penetration: 0.75,
+            dealer: DealerRule::StandSoft17,
+            blackjack_payout: Payout::THREE_TO_TWO,
+            insurance_payout: Payout { win: 2, per: 1 },
+            insurance: true,
+            surrender: true,
+            double_after_split: true,
+            max_split_hands: 4,
+            resplit_aces: false,
+            hit_split_aces: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Hit,
+    Stand,
+    Double,
+    Split,
+    Surrender,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Action::Hit => "hit",
+            Action::Stand => "stand",
+            Action::Double => "double",
+            Action::Split => "split",
+            Action::Surrender => "surrender",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlackjackError {
+    InvalidBet(i64),
+    InsufficientFunds { bet: i64, balance: i64 },
+    IllegalAction(Action),
+}
+
+impl fmt::Display for BlackjackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BlackjackError::InvalidBet(bet) => write!(f, "invalid bet: {}", bet),
+            BlackjackError::InsufficientFunds { bet, balance } => {
+                write!(f, "can't bet {} with a balance of {}", bet, balance)
+            }
+            BlackjackError::IllegalAction(action) => {
+                write!(f, "can't {} on this hand", action)
+            }
+        }
+    }
+}
+
+impl Error for BlackjackError {}
+
+/// Blackjack total of some cards, and whether an Ace is counted as 11
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Total {
+    pub value: u8,
+    pub soft: bool,
+}
+
+impl Total {
+    pub fn of(cards: &[Card]) -> Self {
+        let mut value: u8 = 0;
+        let mut aces = false;
+        for card in cards {
+            value += card_value(card);
+            aces |= card.rank == Rank::Ace;
+        }
+        // At most one Ace can ever count as 11
+        if aces && value + 10 <= 21 {
+            Total {
+                value: value + 10,
+                soft: true,
+            }
+        } else {
+            Total { value, soft: false }
+        }
+    }
+
+    pub fn is_bust(&self) -> bool {
+        self.value > 21
+    }
+}
+
+impl fmt::Display for Total {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.soft {
+            write!(f, "soft {}", self.value)
+        } else {
+            write!(f, "{}", self.value)
+        }
+    }
+}
+
+/// Hard value of a card: Aces count 1, faces 10
+pub fn card_value(card: &Card) -> u8 {
+    match card.rank {
+        Rank::Ace => 1,
+        Rank::Jack | Rank::Queen | Rank::King => 10,
+        rank => rank.value(),
+    }
+}
+
+pub fn is_blackjack(cards: &[Card]) -> bool {
+    cards.len() == 2 && Total::of(cards).value == 21
+}
+
+/// What a strategy sees when it has to act on a hand
+#[derive(Debug, Clone)]
+pub struct Decision<'a> {
+    pub cards: &'a [Card],
+    pub total: Total,
+    pub dealer_up: Card,
+    /// Index of the hand being played, more than one after a split
+    pub hand_index: usize,
+    pub hand_count: usize,
+    pub bet: i64,
+    pub balance: i64,
+    pub can_double: bool,
+    pub can_split: bool,
+    pub can_surrender: bool,
+}
+
+impl Decision<'_> {
+    pub fn is_allowed(&self, action: Action) -> bool {
+        match action {
+            Action::Hit | Action::Stand => true,
+            Action::Double => self.can_double,
+            Action::Split => self.can_split,
+            Action::Surrender => self.can_surrender,
+        }
+    }
+}
+
+/// Plays the player's side of a round: a human at the terminal, or a bot
+pub trait Strategy {
+    fn decide(&mut self, decision: &Decision) -> Action;
+
+    /// Called when the dealer shows an Ace and insurance is offered
+    fn take_insurance(&mut self, _decision: &Decision) -> bool {
+        false
+    }
+}
+
+/// Plays like the dealer: hit below 17, never double or split
+pub struct MimicDealer;
+
+impl Strategy for MimicDealer {
+    fn decide(&mut self, decision: &Decision) -> Action {
+        if decision.total.value < 17 {
+            Action::Hit
+        } else {
+            Action::Stand
+        }
+    }
+}
+
+/// Textbook basic strategy for a multi-deck game where the dealer hits soft 17
+/// (H17): soft 18 doubles against a 2, soft 19 against a 6 and 11 against an
+/// Ace. `Rules::default()` stands on soft 17, where those three plays give up
+/// a little edge; set `dealer: DealerRule::HitSoft17` to play the game the
+/// chart was made for.
+pub struct BasicStrategy;
+
+impl Strategy for BasicStrategy {
+    fn decide(&mut self, d: &Decision) -> Action {
+        use Action::*;
+
+        // Dealer up card as 2..=11
+        let up = match d.dealer_up.rank {
+            Rank::Ace => 11,
+            _ => card_value(&d.dealer_up),
+        };
+        let double_or = |fallback| if d.can_double { Double } else { fallback };
+
+        if d.can_split {
+            let pair = card_value(&d.cards[0]);
+            let split = match pair {
+                1 | 8 => true,
+                2 | 3 | 7 => up <= 7,
+                6 => up <= 6,
+                9 => up <= 9 && up != 7,
+                4 => up == 5 || up == 6,
+                _ => false,
+            };
+            if split {
+                return Split;
+            }
+        }
+
+        let total = d.total.value;
+        if d.can_surrender && !d.total.soft && (total == 16 && up >= 9 || total == 15 && up == 10) {
+            return Surrender;
+        }
+
+        if d.total.soft {
+            return match total {
+                20.. => Stand,
+                19 => {
+                    if up == 6 {
+                        double_or(Stand)
+                    } else {
+                        Stand
+                    }
+                }
+                18 => match up {
+                    2..=6 => double_or(Stand),
+                    7 | 8 => Stand,
+                    _ => Hit,
+                },
+                17 => match up {
+                    3..=6 => double_or(Hit),
+                    _ => Hit,
+                },
+                15 | 16 => match up {
+                    4..=6 => double_or(Hit),
+                    _ => Hit,
+                },
+                _ => match up {
+                    5 | 6 => double_or(Hit),
+                    _ => Hit,
+                },
+            };
+        }
+
+        match total {
+            17.. => Stand,
+            13..=16 => {
+                if up <= 6 {
+                    Stand
+                } else {
+                    Hit
+                }
+            }
+            12 => {
+                if (4..=6).contains(&up) {
+                    Stand
+                } else {
+                    Hit
+                }
+            }
+            11 => double_or(Hit),
+            10 => {
+                if up <= 9 {
+                    double_or(Hit)
+                } else {
+                    Hit
+                }
+            }
+            9 => {
+                if (3..=6).contains(&up) {
+                    double_or(Hit)
+                } else {
+                    Hit
+                }
+            }
+            _ => Hit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Blackjack,
+    Win,
+    Push,
+    Lose,
+    Bust,
+    Surrender,
+}
+
+#[derive(Debug, Clone)]
+pub struct HandResult {
+    pub cards: Hand,
+    pub bet: i64,
+    pub outcome: Outcome,
+    /// Chips won (positive) or lost (negative) on this hand
+    pub net: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct RoundResult {
+    pub hands: Vec<HandResult>,
+    pub dealer: Hand,
+    pub insurance_net: i64,
+    pub net: i64,
+}
+
+/// Running totals for a player at the table
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bankroll {
+    pub balance: i64,
+    pub rounds: u64,
+    pub wagered: i64,
+    pub net: i64,
+}
+
+impl Bankroll {
+    pub fn new(balance: i64) -> Self {
+        Bankroll {
+            balance,
+            ..Bankroll::default()
+        }
+    }
+}
+
+struct PlayerHand {
+    cards: Vec<Card>,
+    bet: i64,
+    split: bool,
+    split_aces: bool,
+    done: bool,
+    surrendered: bool,
+}
+
+impl PlayerHand {
+    fn new(cards: Vec<Card>, bet: i64) -> Self {
+        PlayerHand {
+            cards,
+            bet,
+            split: false,
+            split_aces: false,
+            done: false,
+            surrendered: false,
+        }
+    }
+}
+
+/// A blackjack table for one player, dealing from a shoe.
+///
+/// A table created `with_seed` plays out the same cards every time for the
+/// same sequence of decisions.
+pub struct Table {
+    pub rules: Rules,
+    pub bankroll: Bankroll,
+    shoe: Shoe,
+}
+
+impl Table {
+    pub fn new(rules: Rules, balance: i64) -> Self {
+        Table::with_seed(rules, balance, random_seed())
+    }
+
+    pub fn with_seed(rules: Rules, balance: i64, seed: u64) -> Self {
+        let shoe = Shoe::with_seed(rules.decks.max(1), seed).with_penetration(rules.penetration);
+        Table {
+            rules,
+            bankroll: Bankroll::new(balance),
+            shoe,
+        }
+    }
+
+    /// The shoe being dealt from. Its `rng` is all the randomness the table
+    /// has.
+    pub fn shoe(&self) -> &Shoe {
+        &self.shoe
+    }
+
+    fn draw(&mut self) -> Card {
+        self.shoe
+            .draw_one()
+            .expect("a round never has every card of the shoe out")
+    }
+
+    /// Plays one full round for `bet` chips and settles it against the bankroll
+    pub fn play_round<S: Strategy + ?Sized>(
+        &mut self,
+        bet: i64,
+        strategy: &mut S,
+    ) -> Result<RoundResult, BlackjackError> {
+        if bet <= 0 {
+            return Err(BlackjackError::InvalidBet(bet));
+        }
+        if bet > self.bankroll.balance {
+            return Err(BlackjackError::InsufficientFunds {
+                bet,
+                balance: self.bankroll.balance,
+            });
+        }
+
+        // The cut card came out last round: shuffle before dealing this one
+        self.shoe.reshuffle_if_needed();
+
+        let first = [self.draw(), self.draw()];
+        let mut dealer = vec![self.draw(), self.draw()];
+        let dealer_up = dealer[0];
+        let mut hands = vec![PlayerHand::new(first.to_vec(), bet)];
+
+        let mut insurance = 0;
+        if self.rules.insurance && dealer_up.rank == Rank::Ace {
+            let decision = self.decision(&hands, 0, dealer_up, 0);
+            let cost = bet / 2;
+            if cost > 0 && bet + cost <= self.bankroll.balance && strategy.take_insurance(&decision)
+            {
+                insurance = cost;
+            }
+        }
+
+        let dealer_blackjack = is_blackjack(&dealer);
+        let insurance_net = match (insurance, dealer_blackjack) {
+            (0, _) => 0,
+            (cost, true) => self.rules.insurance_payout.on(cost),
+            (cost, false) => -cost,
+        };
+
+        // Dealer peeks: a dealer blackjack or a player blackjack ends the round
+        if dealer_blackjack || is_blackjack(&hands[0].cards) {
+            let (outcome, net) = match (dealer_blackjack, is_blackjack(&hands[0].cards)) {
+                (true, true) => (Outcome::Push, 0),
+                (true, false) => (Outcome::Lose, -bet),
+                _ => (Outcome::Blackjack, self.rules.blackjack_payout.on(bet)),
+            };
+            let result = HandResult {
+                cards: Hand::from(hands.remove(0).cards),
+                bet,
+                outcome,
+                net,
+            };
+            return Ok(self.settle(vec![result], dealer, insurance, insurance_net));
+        }
+
+        let mut index = 0;
+        while index < hands.len() {
+            while !hands[index].done {
+                if Total::of(&hands[index].cards).value >= 21 {
+                    hands[index].done = true;
+                    break;
+                }
+
+                let decision = self.decision(&hands, index, dealer_up, insurance);
+                let action = strategy.decide(&decision);
+                if !decision.is_allowed(action) {
+                    return Err(BlackjackError::IllegalAction(action));
+                }
+
+                match action {
+                    Action::Hit => {
+                        let card = self.draw();
+                        hands[index].cards.push(card);
+                    }
+                    Action::Stand => hands[index].done = true,
+                    Action::Double => {
+                        let card = self.draw();
+                        let hand = &mut hands[index];
+                        hand.bet *= 2;
+                        hand.cards.push(card);
+                        hand.done = true;
+                    }
+                    Action::Surrender => {
+                        let hand = &mut hands[index];
+                        hand.surrendered = true;
+                        hand.done = true;
+                    }
+                    Action::Split => {
+                        let aces = hands[index].cards[0].rank == Rank::Ace;
+                        let moved = hands[index].cards.pop().expect("a pair to split");
+                        let (left, right) = (self.draw(), self.draw());
+
+                        let mut new_hand = PlayerHand::new(vec![moved, right], bet);
+                        new_hand.split = true;
+                        new_hand.split_aces = aces;
+
+                        let hand = &mut hands[index];
+                        hand.cards.push(left);
+                        hand.split = true;
+                        hand.split_aces = aces;
+
+                        if aces && !self.rules.hit_split_aces {
+                            hand.done = true;
+                            new_hand.done = true;
+                        }
+                        hands.insert(index + 1, new_hand);
+                    }
+                }
+            }
+            index += 1;
+        }
+
+        let dealer_plays = hands
+            .iter()
+            .any(|hand| !hand.surrendered && !Total::of(&hand.cards).is_bust());
+        if dealer_plays {
+            loop {
+                let total = Total::of(&dealer);
+                let hits = total.value < 17
+                    || (total.value == 17
+                        && total.soft
+                        && self.rules.dealer == DealerRule::HitSoft17);
+                if !hits {
+                    break;
+                }
+                dealer.push(self.draw());
+            }
+        }
+
+        let dealer_total = Total::of(&dealer);
+        let results = hands
+            .into_iter()
+            .map(|hand| {
+                let total = Total::of(&hand.cards);
+                let (outcome, net) = if hand.surrendered {
+                    (Outcome::Surrender, -(hand.bet / 2))
+                } else if total.is_bust() {
+                    (Outcome::Bust, -hand.bet)
+                } else if dealer_total.is_bust() || total.value > dealer_total.value {
+                    (Outcome::Win, hand.bet)
+                } else if total.value == dealer_total.value {
+                    (Outcome::Push, 0)
+                } else {
+                    (Outcome::Lose, -hand.bet)
+                };
+                HandResult {
+                    cards: Hand::from(hand.cards),
+                    bet: hand.bet,
+                    outcome,
+                    net,
+                }
+            })
+            .collect();
+
+        Ok(self.settle(results, dealer, insurance, insurance_net))
+    }
+
+    /// What the player may do with hand `index`. Doubling and splitting need
+    /// chips not yet riding on any hand or on `insurance`.
+    fn decision<'a>(
+        &self,
+        hands: &'a [PlayerHand],
+        index: usize,
+        dealer_up: Card,
+        insurance: i64,
+    ) -> Decision<'a> {
+        let hand = &hands[index];
+        let committed: i64 = hands.iter().map(|hand| hand.bet).sum::<i64>() + insurance;
+        let free = self.bankroll.balance - committed;
+        let two_cards = hand.cards.len() == 2;
+
+        let can_double = two_cards
+            && free >= hand.bet
+            && (!hand.split || self.rules.double_after_split)
+            && (!hand.split_aces || self.rules.hit_split_aces);
+        let can_split = two_cards
+            && hand.cards[0].rank == hand.cards[1].rank
+            && hands.len() < self.rules.max_split_hands
+            && free >= hand.bet
+            && (!hand.split_aces || self.rules.resplit_aces);
+        let can_surrender = self.rules.surrender && two_cards && hands.len() == 1;
+
+        Decision {
+            cards: &hand.cards,
+            total: Total::of(&hand.cards),
+            dealer_up,
+            hand_index: index,
+            hand_count: hands.len(),
+            bet: hand.bet,
+            balance: self.bankroll.balance,
+            can_double,
+            can_split,
+            can_surrender,
+        }
+    }
+
+    fn settle(
+        &mut self,
+        hands: Vec<HandResult>,
+        dealer: Vec<Card>,
+        insurance: i64,
+        insurance_net: i64,
+    ) -> RoundResult {
+        let net = hands.iter().map(|hand| hand.net).sum::<i64>() + insurance_net;
+        let wagered = hands.iter().map(|hand| hand.bet).sum::<i64>() + insurance;
+
+        self.bankroll.balance += net;
+        self.bankroll.net += net;
+        self.bankroll.wagered += wagered;
+        self.bankroll.rounds += 1;
+
+        RoundResult {
+            hands,
+            dealer: Hand::from(dealer),
+            insurance_net,
+            net,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::parse_cards;
+
+    /// Deals `cards` in order: player, player, dealer up, dealer hole, then
+    /// whatever the round draws
+    fn table(rules: Rules, balance: i64, cards: &str) -> Table {
+        Table {
+            rules,
+            bankroll: Bankroll::new(balance),
+            shoe: Shoe::stacked(parse_cards(cards).unwrap()),
+        }
+    }
+
+    /// Plays `actions` in turn (standing once they run out) and records what
+    /// each decision allowed
+    #[derive(Default)]
+    struct Scripted {
+        insure: bool,
+        actions: Vec<Action>,
+        allowed: Vec<(bool, bool)>,
+    }
+
+    impl Strategy for Scripted {
+        fn decide(&mut self, decision: &Decision) -> Action {
+            self.allowed.push((decision.can_double, decision.can_split));
+            if self.actions.is_empty() {
+                Action::Stand
+            } else {
+                self.actions.remove(0)
+            }
+        }
+
+        fn take_insurance(&mut self, _decision: &Decision) -> bool {
+            self.insure
+        }
+    }
+
+    #[test]
+    fn blackjack_pays_three_to_two() {
+        let mut table = table(Rules::default(), 100, "As Kd 9c 7h");
+        let result = table.play_round(10, &mut Scripted::default()).unwrap();
+        assert_eq!(result.hands[0].outcome, Outcome::Blackjack);
+        assert_eq!(result.net, 15);
+        assert_eq!(table.bankroll.balance, 115);
+    }
+
+    #[test]
+    fn insurance_pays_two_to_one_against_a_dealer_blackjack() {
+        let mut insured = Scripted {
+            insure: true,
+            ..Scripted::default()
+        };
+        let mut table = table(Rules::default(), 100, "Tc 9d Ah Kh");
+        let result = table.play_round(10, &mut insured).unwrap();
+        assert_eq!(result.hands[0].outcome, Outcome::Lose);
+        assert_eq!(result.insurance_net, 10);
+        assert_eq!(result.net, 0);
+        assert_eq!(table.bankroll.wagered, 15);
+
+        // No dealer blackjack: the side bet is lost, the hand plays on
+        let mut table = Table {
+            shoe: Shoe::stacked(parse_cards("Tc Td Ah 7c").unwrap()),
+            ..table
+        };
+        let result = table.play_round(10, &mut insured).unwrap();
+        assert_eq!(result.hands[0].outcome, Outcome::Win);
+        assert_eq!(result.insurance_net, -5);
+        assert_eq!(result.net, 5);
+    }
+
+    #[test]
+    fn insurance_stake_is_not_available_to_double() {
+        let mut strategy = Scripted {
+            insure: true,
+            ..Scripted::default()
+        };
+        // 24 chips: 10 on the hand and 5 on insurance leave 9, short of a double
+        let mut table = table(Rules::default(), 24, "6c 5d Ah 7c");
+        table.play_round(10, &mut strategy).unwrap();
+        assert_eq!(strategy.allowed, vec![(false, false)]);
+
+        let mut strategy = Scripted::default();
+        let mut table = Table {
+            shoe: Shoe::stacked(parse_cards("6c 5d Ah 7c").unwrap()),
+            bankroll: Bankroll::new(24),
+            ..table
+        };
+        table.play_round(10, &mut strategy).unwrap();
+        assert_eq!(strategy.allowed, vec![(true, false)]);
+    }
+
+    #[test]
+    fn doubling_draws_one_card_for_twice_the_bet() {
+        let mut strategy = Scripted {
+            actions: vec![Action::Double],
+            ..Scripted::default()
+        };
+        let mut table = table(Rules::default(), 100, "6c 5d 9h 7c Th Ks");
+        let result = table.play_round(10, &mut strategy).unwrap();
+        assert_eq!(result.hands[0].bet, 20);
+        assert_eq!(result.hands[0].cards.len(), 3);
+        assert_eq!(result.hands[0].outcome, Outcome::Win);
+        assert_eq!(result.net, 20);
+    }
+
+    #[test]
+    fn splits_stop_at_the_hand_limit_and_the_bankroll() {
+        let rules = Rules {
+            max_split_hands: 2,
+            ..Rules::default()
+        };
+        let mut strategy = Scripted {
+            actions: vec![Action::Split],
+            ..Scripted::default()
+        };
+        // Both split hands are pairs of Eights again, but can't split further
+        let mut table = table(rules, 100, "8c 8d 6h Tc 8h 8s Ts");
+        let result = table.play_round(10, &mut strategy).unwrap();
+        assert_eq!(
+            strategy.allowed,
+            vec![(true, true), (true, false), (true, false)]
+        );
+        assert_eq!(result.hands.len(), 2);
+        assert!(result.hands.iter().all(|hand| hand.outcome == Outcome::Win));
+        assert_eq!(result.net, 20);
+
+        // 15 chips can't cover a second 10 chip hand
+        let mut strategy = Scripted::default();
+        let mut table = Table {
+            shoe: Shoe::stacked(parse_cards("8c 8d 6h Tc Ts").unwrap()),
+            bankroll: Bankroll::new(15),
+            ..table
+        };
+        table.play_round(10, &mut strategy).unwrap();
+        assert_eq!(strategy.allowed, vec![(false, false)]);
+    }
+
+    #[test]
+    fn surrender_gives_up_half_the_bet_without_the_dealer_drawing() {
+        let mut table = table(Rules::default(), 100, "Tc 6d Th 7c");
+        let result = table.play_round(10, &mut BasicStrategy).unwrap();
+        assert_eq!(result.hands[0].outcome, Outcome::Surrender);
+        assert_eq!(result.net, -5);
+        assert_eq!(result.dealer.len(), 2);
+    }
+
+    #[test]
+    fn only_an_h17_dealer_hits_soft_17() {
+        let cards = "Tc 8d 6h Ac 2s";
+        let mut s17 = table(Rules::default(), 100, cards);
+        let result = s17.play_round(10, &mut MimicDealer).unwrap();
+        assert_eq!(result.dealer.len(), 2);
+        assert_eq!(result.hands[0].outcome, Outcome::Win);
+
+        let rules = Rules {
+            dealer: DealerRule::HitSoft17,
+            ..Rules::default()
+        };
+        let mut h17 = table(rules, 100, cards);
+        let result = h17.play_round(10, &mut MimicDealer).unwrap();
+        assert_eq!(result.dealer.len(), 3);
+        assert_eq!(result.hands[0].outcome, Outcome::Lose);
+    }
+}
@@ -0,0 +1,201 @@
+use std::sync::Arc;
+
+use crate::card::{Card, CustomCard, ExtendedCard, JokerColor, Rank, Suit};
+use crate::deck::Deck;
+
+/// Builds decks from a chosen set of ranks and suits, repeated `copies` times.
+///
+/// `DeckBuilder::new()` starts from the standard 52 cards; the presets cover
+/// the usual stripped and doubled decks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeckBuilder {
+    ranks: Vec<Rank>,
+    suits: Vec<Suit>,
+    copies: usize,
+}
+
+impl DeckBuilder {
+    pub fn new() -> Self {
+        DeckBuilder {
+            ranks: Rank::ALL.to_vec(),
+            suits: Suit::ALL.to_vec(),
+            copies: 1,
+        }
+    }
+
+    /// The standard 52-card deck
+    pub fn standard() -> Self {
+        DeckBuilder::new()
+    }
+
+    /// 32-card piquet deck: Seven to Ace in each suit
+    pub fn piquet() -> Self {
+        DeckBuilder::new().ranks_from(Rank::Seven)
+    }
+
+    /// 24-card euchre deck: Nine to Ace in each suit
+    pub fn euchre() -> Self {
+        DeckBuilder::new().ranks_from(Rank::Nine)
+    }
+
+    /// 48-card pinochle deck: two copies of Nine to Ace in each suit
+    pub fn pinochle() -> Self {
+        DeckBuilder::new().ranks_from(Rank::Nine).copies(2)
+    }
+
+    pub fn ranks(mut self, ranks: &[Rank]) -> Self {
+        self.ranks = ranks.to_vec();
+        self
+    }
+
+    /// Keeps `lowest` and every rank above it
+    pub fn ranks_from(mut self, lowest: Rank) -> Self {
+        self.ranks = Rank::ALL
+            .into_iter()
+            .filter(|rank| *rank >= lowest)
+            .collect();
+        self
+    }
+
+    pub fn suits(mut self, suits: &[Suit]) -> Self {
+        self.suits = suits.to_vec();
+        self
+    }
+
+    /// How many times each card appears in the deck
+    pub fn copies(mut self, copies: usize) -> Self {
+        self.copies = copies;
+        self
+    }
+
+    fn cards(&self) -> Vec<Card> {
+        let mut cards = Vec::with_capacity(self.copies * self.suits.len() * self.ranks.len());
+        for _ in 0..self.copies {
+            for suit in &self.suits {
+                for rank in &self.ranks {
+                    cards.push(Card::new(*rank, *suit));
+                }
+            }
+        }
+        cards
+    }
+
+    pub fn build(&self) -> Deck {
+        Deck::from_cards(self.cards())
+    }
+
+    /// Same cards plus `jokers` jokers, alternating black and red
+    pub fn build_with_jokers(&self, jokers: usize) -> Deck<ExtendedCard> {
+        let mut cards: Vec<ExtendedCard> =
+            self.cards().into_iter().map(ExtendedCard::from).collect();
+        for i in 0..jokers {
+            let color = if i % 2 == 0 {
+                JokerColor::Black
+            } else {
+                JokerColor::Red
+            };
+            cards.push(ExtendedCard::Joker(color));
+        }
+        Deck::from_cards(cards)
+    }
+
+    /// The 78-card French tarot deck: 14 cards per suit (with the Knight),
+    /// 21 trumps and the Fool
+    pub fn tarot() -> Deck<ExtendedCard> {
+        let mut cards = Vec::with_capacity(78);
+        for suit in Suit::ALL {
+            for rank in Rank::ALL {
+                if rank == Rank::Queen {
+                    cards.push(ExtendedCard::Knight(suit));
+                }
+                cards.push(ExtendedCard::Standard(Card::new(rank, suit)));
+            }
+        }
+        for number in 1..=21 {
+            cards.push(ExtendedCard::Trump(number));
+        }
+        cards.push(ExtendedCard::Fool);
+        Deck::from_cards(cards)
+    }
+
+    /// A deck of every `rank` in every `suit`, e.g. a Spanish deck with
+    /// `["1", ..., "Rey"]` and `["Oros", "Copas", "Espadas", "Bastos"]`
+    pub fn custom<R: AsRef<str>, S: AsRef<str>>(ranks: &[R], suits: &[S]) -> Deck<CustomCard> {
+        let ranks: Vec<Arc<str>> = ranks.iter().map(|rank| Arc::from(rank.as_ref())).collect();
+        let suits: Vec<Arc<str>> = suits.iter().map(|suit| Arc::from(suit.as_ref())).collect();
+
+        let mut cards = Vec::with_capacity(ranks.len() * suits.len());
+        for (s, suit) in suits.iter().enumerate() {
+            for (r, rank) in ranks.iter().enumerate() {
+                cards.push(CustomCard::new(r, rank.clone(), s, suit.clone()));
+            }
+        }
+        Deck::from_cards(cards)
+    }
+}
+
+impl Default for DeckBuilder {
+    fn default() -> Self {
+        DeckBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_strip_and_double_the_standard_deck() {
+        assert_eq!(DeckBuilder::standard().build().remaining(), 52);
+        let piquet = DeckBuilder::piquet().build();
+        assert_eq!(piquet.remaining(), 32);
+        assert!(piquet.cards.iter().all(|card| card.rank >= Rank::Seven));
+        assert_eq!(DeckBuilder::euchre().build().remaining(), 24);
+
+        let pinochle = DeckBuilder::pinochle().build();
+        assert_eq!(pinochle.remaining(), 48);
+        let nine = Card::new(Rank::Nine, Suit::Spades);
+        assert_eq!(
+            pinochle.cards.iter().filter(|card| **card == nine).count(),
+            2
+        );
+
+        let hearts = DeckBuilder::new()
+            .suits(&[Suit::Hearts])
+            .ranks(&[Rank::Ace, Rank::King])
+            .build();
+        assert_eq!(
+            hearts.cards,
+            [
+                Card::new(Rank::Ace, Suit::Hearts),
+                Card::new(Rank::King, Suit::Hearts)
+            ]
+        );
+    }
+
+    #[test]
+    fn adds_jokers_and_builds_tarot_and_custom_decks() {
+        let deck = DeckBuilder::euchre().build_with_jokers(2);
+        assert_eq!(deck.remaining(), 26);
+        assert_eq!(
+            deck.cards[24..],
+            [
+                ExtendedCard::Joker(JokerColor::Black),
+                ExtendedCard::Joker(JokerColor::Red)
+            ]
+        );
+
+        let tarot = DeckBuilder::tarot();
+        assert_eq!(tarot.remaining(), 78);
+        let count =
+            |wanted: fn(&ExtendedCard) -> bool| tarot.cards.iter().filter(|c| wanted(c)).count();
+        assert_eq!(count(|card| matches!(card, ExtendedCard::Trump(_))), 21);
+        assert_eq!(count(|card| matches!(card, ExtendedCard::Knight(_))), 4);
+        assert_eq!(count(|card| *card == ExtendedCard::Fool), 1);
+
+        let spanish = DeckBuilder::custom(&["1", "2", "Rey"], &["Oros", "Copas"]);
+        assert_eq!(spanish.remaining(), 6);
+        let top = spanish.peek().unwrap();
+        assert_eq!((top.rank_name(), top.suit_name()), ("Rey", "Copas"));
+    }
+}
@@ -0,0 +1,203 @@
+use std::fmt;
+use std::sync::Arc;
+
+/// The four French suits.
+///
+/// Variants are declared in bridge order (clubs lowest, spades highest) so the
+/// derived `Ord` can be used to break ties when sorting cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+    /// Hearts and Diamonds are red, Clubs and Spades black
+    pub fn is_red(&self) -> bool {
+        matches!(self, Suit::Hearts | Suit::Diamonds)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Suit::Clubs => "Clubs",
+            Suit::Diamonds => "Diamonds",
+            Suit::Hearts => "Hearts",
+            Suit::Spades => "Spades",
+        }
+    }
+}
+
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Card ranks, from Two up to Ace.
+///
+/// Ace is high: it is the last variant so `Rank::Ace > Rank::King`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Rank {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl Rank {
+    pub const ALL: [Rank; 13] = [
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+        Rank::Ace,
+    ];
+
+    /// Numeric value of the rank, 2 to 14 (Ace high)
+    pub fn value(&self) -> u8 {
+        *self as u8 + 2
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rank::Two => "Two",
+            Rank::Three => "Three",
+            Rank::Four => "Four",
+            Rank::Five => "Five",
+            Rank::Six => "Six",
+            Rank::Seven => "Seven",
+            Rank::Eight => "Eight",
+            Rank::Nine => "Nine",
+            Rank::Ten => "Ten",
+            Rank::Jack => "Jack",
+            Rank::Queen => "Queen",
+            Rank::King => "King",
+            Rank::Ace => "Ace",
+        }
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A single playing card.
+///
+/// Cards order by rank first, then by suit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Card {
+    pub rank: Rank,
+    pub suit: Suit,
+}
+
+impl Card {
+    pub fn new(rank: Rank, suit: Suit) -> Self {
+        Card { rank, suit }
+    }
+}
+
+// Display gives us `to_string()` for free: "Ace of Hearts"
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} of {}", self.rank, self.suit)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum JokerColor {
+    Black,
+    Red,
+}
+
+/// A card from a deck that goes beyond the 52 French cards: jokers and the
+/// extra cards of a French tarot deck.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ExtendedCard {
+    Standard(Card),
+    /// The tarot Knight (Cavalier)
+    Knight(Suit),
+    /// Tarot trumps, numbered 1 to 21
+    Trump(u8),
+    /// The tarot Fool (the Excuse)
+    Fool,
+    Joker(JokerColor),
+}
+
+impl From<Card> for ExtendedCard {
+    fn from(card: Card) -> Self {
+        ExtendedCard::Standard(card)
+    }
+}
+
+impl fmt::Display for ExtendedCard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExtendedCard::Standard(card) => write!(f, "{}", card),
+            ExtendedCard::Knight(suit) => write!(f, "Knight of {}", suit),
+            ExtendedCard::Trump(number) => write!(f, "Trump {}", number),
+            ExtendedCard::Fool => f.write_str("The Fool"),
+            ExtendedCard::Joker(JokerColor::Black) => f.write_str("Black Joker"),
+            ExtendedCard::Joker(JokerColor::Red) => f.write_str("Red Joker"),
+        }
+    }
+}
+
+/// A card from a user-defined deck, e.g. a Spanish deck of Cups and Coins.
+///
+/// `rank` and `suit` index into the names the deck was built with and drive
+/// the ordering; the names themselves are shared between all the cards.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CustomCard {
+    pub rank: usize,
+    pub suit: usize,
+    rank_name: Arc<str>,
+    suit_name: Arc<str>,
+}
+
+impl CustomCard {
+    pub fn new(rank: usize, rank_name: Arc<str>, suit: usize, suit_name: Arc<str>) -> Self {
+        CustomCard {
+            rank,
+            suit,
+            rank_name,
+            suit_name,
+        }
+    }
+
+    pub fn rank_name(&self) -> &str {
+        &self.rank_name
+    }
+
+    pub fn suit_name(&self) -> &str {
+        &self.suit_name
+    }
+}
+
+impl fmt::Display for CustomCard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} of {}", self.rank_name, self.suit_name)
+    }
+}
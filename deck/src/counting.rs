@@ -0,0 +1,140 @@
+use crate::card::{Card, Rank, Suit};
+
+/// Cards in one standard deck, the unit of the true count
+pub(crate) const DECK_SIZE: f64 = 52.0;
+
+/// Hi-Lo tag of a card: +1 for Two to Six, 0 for Seven to Nine, -1 for tens
+/// and Aces
+pub fn hi_lo(card: &Card) -> i32 {
+    match card.rank {
+        Rank::Two | Rank::Three | Rank::Four | Rank::Five | Rank::Six => 1,
+        Rank::Seven | Rank::Eight | Rank::Nine => 0,
+        _ => -1,
+    }
+}
+
+/// Questions about the cards left to draw, implemented by `Deck` and `Shoe`.
+///
+/// Probabilities treat the remaining cards as unknown and evenly shuffled, and
+/// are computed exactly from what is left rather than from a fresh deck.
+pub trait CardCounting {
+    /// The cards still to be drawn, in any order
+    fn remaining_cards(&self) -> &[Card];
+
+    /// Cards of the Hi-Lo count seen so far.
+    ///
+    /// By default this is worked out from what is left, which assumes the
+    /// cards started out as whole standard decks (their tags add up to 0).
+    fn running_count(&self) -> i32 {
+        -self.remaining_cards().iter().map(hi_lo).sum::<i32>()
+    }
+
+    /// Decks' worth of cards not seen yet, the divisor of the true count
+    fn decks_remaining(&self) -> f64 {
+        self.remaining_cards().len() as f64 / DECK_SIZE
+    }
+
+    /// Running count per deck remaining, 0 once nothing is left
+    fn true_count(&self) -> f64 {
+        let decks = self.decks_remaining();
+        if decks == 0.0 {
+            0.0
+        } else {
+            self.running_count() as f64 / decks
+        }
+    }
+
+    /// How many of the remaining cards satisfy `matches`
+    fn count_remaining<F: Fn(&Card) -> bool>(&self, matches: F) -> usize {
+        self.remaining_cards()
+            .iter()
+            .filter(|card| matches(card))
+            .count()
+    }
+
+    /// Chance that the next card satisfies `matches`
+    fn probability_next<F: Fn(&Card) -> bool>(&self, matches: F) -> f64 {
+        let total = self.remaining_cards().len();
+        if total == 0 {
+            return 0.0;
+        }
+        self.count_remaining(matches) as f64 / total as f64
+    }
+
+    fn probability_next_rank(&self, rank: Rank) -> f64 {
+        self.probability_next(|card| card.rank == rank)
+    }
+
+    fn probability_next_suit(&self, suit: Suit) -> f64 {
+        self.probability_next(|card| card.suit == suit)
+    }
+
+    fn probability_next_card(&self, card: &Card) -> f64 {
+        self.probability_next(|other| other == card)
+    }
+
+    /// Chance that at least one of the next `draws` cards satisfies `matches`
+    fn probability_within<F: Fn(&Card) -> bool>(&self, draws: usize, matches: F) -> f64 {
+        let total = self.remaining_cards().len();
+        let hits = self.count_remaining(matches);
+        1.0 - probability_of_none(total, hits, draws)
+    }
+
+    /// Chance that at least one of `cards` (e.g. a player's outs) comes in the
+    /// next `draws` cards
+    fn probability_any_within(&self, cards: &[Card], draws: usize) -> f64 {
+        self.probability_within(draws, |card| cards.contains(card))
+    }
+}
+
+/// Hypergeometric chance of missing all `hits` cards among `total` when
+/// drawing `draws` of them
+fn probability_of_none(total: usize, hits: usize, draws: usize) -> f64 {
+    let draws = draws.min(total);
+    if draws > total - hits {
+        return 0.0;
+    }
+    (0..draws)
+        .map(|i| (total - hits - i) as f64 / (total - i) as f64)
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::Deck;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-12
+    }
+
+    #[test]
+    fn counts_what_has_left_the_deck() {
+        // An unshuffled deck has the Spades on top, Ace first
+        let mut deck = Deck::new();
+        deck.deal(4).unwrap();
+        assert_eq!(deck.running_count(), -4);
+        assert!(close(deck.decks_remaining(), 48.0 / 52.0));
+        assert!(close(deck.true_count(), -4.0 * 52.0 / 48.0));
+
+        deck.deal(48).unwrap();
+        assert_eq!(deck.running_count(), 0);
+        assert_eq!(deck.true_count(), 0.0);
+        assert_eq!(deck.probability_next_rank(Rank::Ace), 0.0);
+    }
+
+    #[test]
+    fn draw_probabilities_are_exact_for_what_is_left() {
+        let mut deck = Deck::new();
+        deck.deal(4).unwrap();
+        assert!(close(deck.probability_next_rank(Rank::Ace), 3.0 / 48.0));
+        assert!(close(deck.probability_next_suit(Suit::Spades), 9.0 / 48.0));
+        let ace = Card::new(Rank::Ace, Suit::Clubs);
+        assert!(close(deck.probability_next_card(&ace), 1.0 / 48.0));
+
+        let any_ace = deck.probability_within(2, |card| card.rank == Rank::Ace);
+        assert!(close(any_ace, 1.0 - (45.0 / 48.0) * (44.0 / 47.0)));
+        assert_eq!(deck.probability_any_within(&[ace], 0), 0.0);
+        assert_eq!(deck.probability_any_within(&[ace], 48), 1.0);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::deck::{DealError, Deck};
+use crate::hand::Hand;
+
+/// How cards go around the table.
+///
+/// Each batch is dealt to every seat in turn before the next batch starts, so
+/// `round_robin` is batches of one card and euchre's "threes then twos" is
+/// `batches(4, &[3, 2])`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DealPlan {
+    seats: usize,
+    batches: Vec<usize>,
+    first_seat: usize,
+    kitty: usize,
+    community: usize,
+}
+
+impl DealPlan {
+    /// One card at a time to each of `seats` seats until each has `cards_per_seat`
+    pub fn round_robin(seats: usize, cards_per_seat: usize) -> Self {
+        DealPlan::batches(seats, &vec![1; cards_per_seat])
+    }
+
+    /// `batches[0]` cards to every seat, then `batches[1]` to every seat, ...
+    pub fn batches(seats: usize, batches: &[usize]) -> Self {
+        DealPlan {
+            seats,
+            batches: batches.to_vec(),
+            first_seat: 0,
+            kitty: 0,
+            community: 0,
+        }
+    }
+
+    /// Seat that receives the first card, usually the one left of the dealer
+    pub fn starting_at(mut self, seat: usize) -> Self {
+        self.first_seat = seat;
+        self
+    }
+
+    /// Cards set aside face down after the hands, e.g. the euchre kitty or
+    /// the skat
+    pub fn with_kitty(mut self, cards: usize) -> Self {
+        self.kitty = cards;
+        self
+    }
+
+    /// Cards dealt to the middle of the table after the hands and kitty
+    pub fn with_community(mut self, cards: usize) -> Self {
+        self.community = cards;
+        self
+    }
+
+    pub fn seats(&self) -> usize {
+        self.seats
+    }
+
+    pub fn cards_per_seat(&self) -> usize {
+        self.batches.iter().sum()
+    }
+
+    /// Total number of cards the plan takes from the deck
+    pub fn cards_needed(&self) -> usize {
+        self.seats * self.cards_per_seat() + self.kitty + self.community
+    }
+
+    /// Seats in the order they receive cards
+    fn seat_order(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.seats).map(|i| (self.first_seat + i) % self.seats)
+    }
+}
+
+/// Result of dealing a `DealPlan`. `hands[i]` belongs to seat `i`, whoever was
+/// dealt first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableDeal<C> {
+    pub hands: Vec<Hand<C>>,
+    pub kitty: Hand<C>,
+    pub community: Hand<C>,
+}
+
+impl<C> Deck<C> {
+    /// Deals from the top following `plan`. Nothing is dealt if the deck is
+    /// too short to complete the plan.
+    pub fn deal_table(&mut self, plan: &DealPlan) -> Result<TableDeal<C>, DealError> {
+        let needed = plan.cards_needed();
+        if needed > self.remaining() {
+            return Err(DealError::NotEnoughCards {
+                requested: needed,
+                remaining: self.remaining(),
+            });
+        }
+
+        let mut hands: Vec<Hand<C>> = (0..plan.seats).map(|_| Hand::new()).collect();
+        for batch in &plan.batches {
+            for seat in plan.seat_order() {
+                for _ in 0..*batch {
+                    hands[seat].extend(self.draw_one());
+                }
+            }
+        }
+
+        let kitty = self.deal_up_to(plan.kitty);
+        let community = self.deal_up_to(plan.community);
+        Ok(TableDeal {
+            hands,
+            kitty,
+            community,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::DeckBuilder;
+
+    fn hand(cards: &str) -> Hand {
+        cards.parse().unwrap()
+    }
+
+    #[test]
+    fn deals_euchre_threes_then_twos_from_the_dealers_left() {
+        // Unshuffled, so the Ace of Spades is on top
+        let mut deck = DeckBuilder::euchre().build();
+        let plan = DealPlan::batches(4, &[3, 2]).starting_at(1).with_kitty(4);
+        assert_eq!(plan.cards_needed(), 24);
+
+        let deal = deck.deal_table(&plan).unwrap();
+        assert_eq!(deal.hands[1], hand("As Ks Qs Ad Kd"));
+        assert_eq!(deal.hands[2], hand("Js Ts 9s Qd Jd"));
+        assert_eq!(deal.hands[3], hand("Ah Kh Qh Td 9d"));
+        assert_eq!(deal.hands[0], hand("Jh Th 9h Ac Kc"));
+        assert_eq!(deal.kitty, hand("9c Tc Jc Qc"));
+        assert!(deal.community.is_empty());
+        assert!(deck.is_empty());
+    }
+
+    #[test]
+    fn deals_nothing_when_the_plan_needs_too_many_cards() {
+        let mut deck = DeckBuilder::new().build();
+        let plan = DealPlan::round_robin(10, 2).with_community(5);
+        assert_eq!(plan.cards_needed(), 25);
+        let deal = deck.deal_table(&plan).unwrap();
+        assert_eq!(deal.community.len(), 5);
+        assert!(deal.hands.iter().all(|hand| hand.len() == 2));
+
+        let plan = DealPlan::round_robin(10, 3);
+        assert_eq!(
+            deck.deal_table(&plan),
+            Err(DealError::NotEnoughCards {
+                requested: 30,
+                remaining: 27
+            })
+        );
+        assert_eq!(deck.remaining(), 27);
+    }
+}
@@ -0,0 +1,404 @@
+use std::error::Error;
+use std::fmt;
+
+use rand::RngCore;
+
+use crate::card::{Card, Rank, Suit};
+use crate::counting::CardCounting;
+use crate::hand::Hand;
+use crate::shuffle::{ShuffleSequence, bounded_index, fisher_yates, random_seed, seeded_rng};
+use crate::source::CardSource;
+
+/// Returned when the deck can't honour a deal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DealError {
+    NotEnoughCards {
+        requested: usize,
+        remaining: usize,
+    },
+    /// A cut or insert position past the bottom of the deck
+    PositionOutOfRange {
+        position: usize,
+        remaining: usize,
+    },
+}
+
+impl fmt::Display for DealError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DealError::NotEnoughCards {
+                requested,
+                remaining,
+            } => write!(
+                f,
+                "can't deal {} cards, only {} remaining",
+                requested, remaining
+            ),
+            DealError::PositionOutOfRange {
+                position,
+                remaining,
+            } => write!(
+                f,
+                "position {} is outside a deck of {} cards",
+                position, remaining
+            ),
+        }
+    }
+}
+
+impl Error for DealError {}
+
+/// A pile of cards. The top of the deck is the end of `cards`.
+///
+/// Standard French cards by default; `DeckBuilder` makes decks of jokers,
+/// tarot or custom cards, which shuffle and deal the same way.
+#[derive(Debug, Clone)]
+pub struct Deck<C = Card> {
+    pub cards: Vec<C>,
+    // Seed of the last seeded shuffle, kept so a game can be replayed
+    seed: Option<u64>,
+    // Played cards, top of the pile at the end, like `cards`
+    discards: Vec<C>,
+}
+
+impl Deck {
+    /// Builds a standard 52-card deck, suit by suit, Two to Ace
+    pub fn new() -> Self {
+        let mut cards = Vec::with_capacity(52);
+
+        for suit in Suit::ALL {
+            for rank in Rank::ALL {
+                cards.push(Card::new(rank, suit));
+            }
+        }
+
+        // No return statements, like Kotlin ?
+        // Implicit return (important no semicolon ;)
+        Deck {
+            cards,
+            seed: None,
+            discards: vec![],
+        }
+    }
+}
+
+impl<C> Deck<C> {
+    /// A deck holding exactly `cards`, the last one on top
+    pub fn from_cards(cards: Vec<C>) -> Self {
+        Deck {
+            cards,
+            seed: None,
+            discards: vec![],
+        }
+    }
+
+    /// Puts a saved deck back together
+    pub(crate) fn restore(cards: Vec<C>, seed: Option<u64>, discards: Vec<C>) -> Self {
+        Deck {
+            cards,
+            seed,
+            discards,
+        }
+    }
+
+    /// Shuffles with a freshly picked seed and returns it.
+    ///
+    /// Store the returned seed with the game: `shufle_seeded` with the same
+    /// seed on a new deck reproduces the exact same order.
+    pub fn shufle(&mut self) -> u64 {
+        let seed = random_seed();
+        self.shufle_seeded(seed);
+        seed
+    }
+
+    /// Shuffles deterministically: the same seed always gives the same order,
+    /// across runs and platforms
+    pub fn shufle_seeded(&mut self, seed: u64) {
+        let mut rng = seeded_rng(seed);
+        fisher_yates(&mut self.cards, &mut rng);
+        self.seed = Some(seed);
+    }
+
+    /// Shuffles with a caller-supplied RNG. The deck forgets any previous seed
+    /// since it can't know how `rng` was created.
+    pub fn shufle_with<R: RngCore + ?Sized>(&mut self, rng: &mut R) {
+        fisher_yates(&mut self.cards, rng);
+        self.seed = None;
+    }
+
+    /// Shuffles the way a person would, e.g. with `ShuffleSequence::casino()`.
+    /// Hand shuffles are not uniform: see `shuffle::quality` to measure how far off
+    pub fn shufle_by<R: RngCore + ?Sized>(&mut self, sequence: &ShuffleSequence, rng: &mut R) {
+        sequence.apply(&mut self.cards, rng);
+        self.seed = None;
+    }
+
+    /// Seed of the last `shufle` / `shufle_seeded` call, if any
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// Deals exactly `num_cards` from the top, or nothing at all
+    pub fn deal(&mut self, num_cards: usize) -> Result<Hand<C>, DealError> {
+        let remaining = self.cards.len();
+        if num_cards > remaining {
+            return Err(DealError::NotEnoughCards {
+                requested: num_cards,
+                remaining,
+            });
+        }
+
+        Ok(Hand::from(self.cards.split_off(remaining - num_cards)))
+    }
+
+    /// Deals `num_cards` from the top, or whatever is left if the deck runs short
+    pub fn deal_up_to(&mut self, num_cards: usize) -> Hand<C> {
+        let at = self.cards.len().saturating_sub(num_cards);
+        Hand::from(self.cards.split_off(at))
+    }
+
+    pub fn draw_one(&mut self) -> Option<C> {
+        self.cards.pop()
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Moves the top `position` cards to the bottom, keeping their order
+    pub fn cut(&mut self, position: usize) -> Result<(), DealError> {
+        self.check_position(position)?;
+        self.cards.rotate_right(position);
+        Ok(())
+    }
+
+    /// Cuts at a uniformly random position
+    pub fn cut_random<R: RngCore + ?Sized>(&mut self, rng: &mut R) {
+        let position = bounded_index(rng, self.cards.len() + 1);
+        self.cards.rotate_right(position);
+    }
+
+    /// Top card, without taking it
+    pub fn peek(&self) -> Option<&C> {
+        self.cards.last()
+    }
+
+    /// Up to `num_cards` from the top without taking them, top card first
+    pub fn peek_n(&self, num_cards: usize) -> impl Iterator<Item = &C> {
+        self.cards.iter().rev().take(num_cards)
+    }
+
+    pub fn draw_bottom(&mut self) -> Option<C> {
+        if self.cards.is_empty() {
+            None
+        } else {
+            Some(self.cards.remove(0))
+        }
+    }
+
+    pub fn put_on_top(&mut self, card: C) {
+        self.cards.push(card);
+    }
+
+    pub fn put_on_bottom(&mut self, card: C) {
+        self.cards.insert(0, card);
+    }
+
+    /// Inserts `card` so that `position` cards sit above it (0 is the top)
+    pub fn insert(&mut self, position: usize, card: C) -> Result<(), DealError> {
+        self.check_position(position)?;
+        self.cards.insert(self.cards.len() - position, card);
+        Ok(())
+    }
+
+    /// Inserts `card` at a uniformly random position, returns that position
+    /// counted from the top
+    pub fn insert_random<R: RngCore + ?Sized>(&mut self, card: C, rng: &mut R) -> usize {
+        let position = bounded_index(rng, self.cards.len() + 1);
+        self.cards.insert(self.cards.len() - position, card);
+        position
+    }
+
+    fn check_position(&self, position: usize) -> Result<(), DealError> {
+        if position > self.cards.len() {
+            return Err(DealError::PositionOutOfRange {
+                position,
+                remaining: self.cards.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Puts a played card face up on the discard pile
+    pub fn discard(&mut self, card: C) {
+        self.discards.push(card);
+    }
+
+    pub fn discard_hand(&mut self, hand: Hand<C>) {
+        self.discards.extend(hand);
+    }
+
+    /// The discard pile, top card last
+    pub fn discards(&self) -> &[C] {
+        &self.discards
+    }
+
+    /// Shuffles the discard pile and slides it under the remaining cards.
+    /// Returns how many cards came back.
+    pub fn recycle_discards<R: RngCore + ?Sized>(&mut self, rng: &mut R) -> usize {
+        let mut recycled = std::mem::take(&mut self.discards);
+        fisher_yates(&mut recycled, rng);
+        let count = recycled.len();
+        recycled.append(&mut self.cards);
+        self.cards = recycled;
+        count
+    }
+
+    /// Draws the top card, first reshuffling the discard pile back in if the
+    /// deck has run out
+    pub fn draw_or_recycle<R: RngCore + ?Sized>(&mut self, rng: &mut R) -> Option<C> {
+        if self.cards.is_empty() {
+            self.recycle_discards(rng);
+        }
+        self.cards.pop()
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Deck::new()
+    }
+}
+
+impl CardSource for Deck {
+    fn draw_one(&mut self) -> Option<Card> {
+        Deck::draw_one(self)
+    }
+
+    fn remaining(&self) -> usize {
+        Deck::remaining(self)
+    }
+
+    fn deal(&mut self, num_cards: usize) -> Result<Hand, DealError> {
+        Deck::deal(self, num_cards)
+    }
+
+    fn deal_up_to(&mut self, num_cards: usize) -> Hand {
+        Deck::deal_up_to(self, num_cards)
+    }
+}
+
+/// The running count is read off the remaining cards, so it is only right for
+/// a deck that started as whole standard decks
+impl CardCounting for Deck {
+    fn remaining_cards(&self) -> &[Card] {
+        &self.cards
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::parse_cards;
+
+    // Pinned so a change to the RNG, `bounded_index` or `fisher_yates` can't
+    // slip through: every seed recorded with a game would replay differently.
+    const SEED_42: &str = "9h 2h Jd 7h Jh Qd 7c 9s 2c 8s 8h Ah 2s Kc 6h 4d Ad Jc Qc As 7d 3d \
+                           9c Kh 5d 5c Th 3c 2d Ks 8d Tc 8c 4h 4s Qh Js 4c Kd 6s 6d 9d 5s Qs \
+                           Td Ts 3s 7s Ac 6c 3h 5h";
+
+    #[test]
+    fn seeded_shuffle_matches_golden_order() {
+        let mut deck = Deck::new();
+        deck.shufle_seeded(42);
+        assert_eq!(deck.cards, parse_cards(SEED_42).unwrap());
+        assert_eq!(deck.seed(), Some(42));
+    }
+
+    #[test]
+    fn recorded_seed_replays_the_same_order() {
+        let mut first = Deck::new();
+        let seed = first.shufle();
+
+        let mut replay = Deck::new();
+        replay.shufle_seeded(seed);
+        assert_eq!(first.cards, replay.cards);
+    }
+
+    #[test]
+    fn deals_from_the_top_or_not_at_all() {
+        let mut deck = Deck::from_cards(parse_cards("2c 3c 4c 5c").unwrap());
+        let hand = deck.deal(3).unwrap();
+        assert_eq!(hand.cards(), parse_cards("3c 4c 5c").unwrap());
+        assert_eq!(deck.remaining(), 1);
+
+        assert_eq!(
+            deck.deal(2),
+            Err(DealError::NotEnoughCards {
+                requested: 2,
+                remaining: 1
+            })
+        );
+        assert_eq!(deck.remaining(), 1, "a failed deal takes nothing");
+
+        assert_eq!(deck.deal_up_to(5).cards(), parse_cards("2c").unwrap());
+        assert!(deck.is_empty());
+        assert_eq!(deck.draw_one(), None);
+    }
+
+    #[test]
+    fn cuts_peeks_and_inserts_counting_from_the_top() {
+        let cards = |text| parse_cards(text).unwrap();
+        let card = |text: &str| text.parse::<Card>().unwrap();
+        let mut deck = Deck::from_cards(cards("2c 3c 4c 5c 6c"));
+        deck.cut(2).unwrap();
+        assert_eq!(deck.cards, cards("5c 6c 2c 3c 4c"));
+        assert_eq!(
+            deck.cut(6),
+            Err(DealError::PositionOutOfRange {
+                position: 6,
+                remaining: 5
+            })
+        );
+
+        assert_eq!(deck.peek(), Some(&card("4c")));
+        assert_eq!(deck.peek_n(2).copied().collect::<Vec<_>>(), cards("4c 3c"));
+        assert_eq!(deck.draw_bottom(), Some(card("5c")));
+
+        let ace = card("As");
+        deck.insert(1, ace).unwrap();
+        assert_eq!(deck.cards, cards("6c 2c 3c As 4c"));
+        deck.put_on_bottom(ace);
+        deck.put_on_top(ace);
+        assert_eq!((deck.cards[0], deck.peek()), (ace, Some(&ace)));
+        assert!(deck.insert(8, ace).is_err());
+    }
+
+    #[test]
+    fn recycles_the_discards_under_the_stock() {
+        let cards = |text| parse_cards(text).unwrap();
+        let card = |text: &str| text.parse::<Card>().unwrap();
+        let mut deck = Deck::from_cards(cards("2c 3c"));
+        let mut rng = seeded_rng(5);
+        deck.discard(card("Kd"));
+        deck.discard_hand(Hand::from(cards("Qd Jd")));
+        assert_eq!(deck.discards(), cards("Kd Qd Jd"));
+
+        assert_eq!(deck.draw_or_recycle(&mut rng), Some(card("3c")));
+        assert_eq!(deck.draw_or_recycle(&mut rng), Some(card("2c")));
+        assert_eq!(deck.remaining(), 0);
+
+        // Empty now: the three discards come back, shuffled, in one go
+        let drawn = deck.draw_or_recycle(&mut rng).unwrap();
+        assert!(deck.discards().is_empty());
+        let mut rest = deck.cards.clone();
+        rest.push(drawn);
+        rest.sort();
+        assert_eq!(rest, cards("Jd Qd Kd"));
+        assert_eq!(deck.recycle_discards(&mut rng), 0);
+    }
+}
@@ -0,0 +1,256 @@
+use std::error::Error;
+use std::fmt;
+
+use rand_chacha::ChaCha8Rng;
+
+use crate::card::{Card, Rank, Suit};
+use crate::deck::Deck;
+use crate::hand::Hand;
+use crate::shuffle::seeded_rng;
+
+pub const HAND_SIZE: usize = 7;
+/// Seven hands of seven leave the starter card and two in the stock
+pub const MAX_PLAYERS: usize = 7;
+
+/// Whether `card` can go on `top` while `suit` is the suit to follow (the
+/// top card's suit, or whatever was called on an Eight). Eights go on
+/// anything.
+pub fn can_play(card: &Card, top: &Card, suit: Suit) -> bool {
+    card.rank == Rank::Eight || card.suit == suit || card.rank == top.rank
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayError {
+    NotYourTurn,
+    NotInHand(Card),
+    /// The card matches neither the suit nor the rank on the pile
+    DoesNotMatch(Card),
+    /// An Eight was played without calling a suit
+    NoSuitCalled,
+    GameOver,
+}
+
+impl fmt::Display for PlayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlayError::NotYourTurn => f.write_str("not your turn"),
+            PlayError::NotInHand(card) => write!(f, "you don't hold the {}", card),
+            PlayError::DoesNotMatch(card) => {
+                write!(f, "the {} matches neither suit nor rank", card)
+            }
+            PlayError::NoSuitCalled => f.write_str("an Eight needs a suit to be called"),
+            PlayError::GameOver => f.write_str("the game is over"),
+        }
+    }
+}
+
+impl Error for PlayError {}
+
+/// `CrazyEights::deal` needs between 2 and `MAX_PLAYERS` players
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerCountError(pub usize);
+
+impl fmt::Display for PlayerCountError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Crazy Eights is played by 2 to {} players, not {}",
+            MAX_PLAYERS, self.0
+        )
+    }
+}
+
+impl Error for PlayerCountError {}
+
+/// What happened when a player drew
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Drawn {
+    Card(Card),
+    /// Stock and discard pile are both used up, the turn just passes
+    Nothing,
+}
+
+/// Two-or-more player Crazy Eights.
+///
+/// On your turn play a card matching the suit or rank of the top of the pile,
+/// or an Eight and call a new suit; otherwise draw one card, which ends your
+/// turn. First to empty their hand wins. When the stock runs out the pile
+/// under the top card is shuffled back in.
+#[derive(Debug, Clone)]
+pub struct CrazyEights {
+    deck: Deck,
+    hands: Vec<Hand>,
+    top: Card,
+    suit: Suit,
+    turn: usize,
+    winner: Option<usize>,
+    // Turns in a row where nobody could play or draw
+    passes: usize,
+    rng: ChaCha8Rng,
+}
+
+impl CrazyEights {
+    /// Shuffles from `seed` and deals `HAND_SIZE` cards to each of `players`
+    pub fn deal(players: usize, seed: u64) -> Result<Self, PlayerCountError> {
+        if !(2..=MAX_PLAYERS).contains(&players) {
+            return Err(PlayerCountError(players));
+        }
+        let mut rng = seeded_rng(seed);
+        let mut deck = Deck::new();
+        deck.shufle_with(&mut rng);
+
+        let hands: Vec<Hand> = (0..players).map(|_| deck.deal_up_to(HAND_SIZE)).collect();
+        let top = deck
+            .draw_one()
+            .expect("MAX_PLAYERS hands leave a starter card");
+        Ok(CrazyEights {
+            deck,
+            hands,
+            top,
+            suit: top.suit,
+            turn: 0,
+            winner: None,
+            passes: 0,
+            rng,
+        })
+    }
+
+    /// The RNG that reshuffles the pile into the stock, for
+    /// `SavedGame::with_rng`
+    pub fn rng(&self) -> &ChaCha8Rng {
+        &self.rng
+    }
+
+    pub fn players(&self) -> usize {
+        self.hands.len()
+    }
+
+    pub fn hand(&self, player: usize) -> &Hand {
+        &self.hands[player]
+    }
+
+    pub fn top(&self) -> Card {
+        self.top
+    }
+
+    /// The suit to follow
+    pub fn suit(&self) -> Suit {
+        self.suit
+    }
+
+    /// Whose turn it is
+    pub fn turn(&self) -> usize {
+        self.turn
+    }
+
+    pub fn stock_len(&self) -> usize {
+        self.deck.remaining()
+    }
+
+    /// `Some(player)` once someone has gone out
+    pub fn winner(&self) -> Option<usize> {
+        self.winner
+    }
+
+    /// Over when someone has won, or when a whole round went by without
+    /// anyone able to play or draw
+    pub fn is_over(&self) -> bool {
+        self.winner.is_some() || self.passes >= self.players()
+    }
+
+    /// Cards `player` could legally play right now
+    pub fn playable(&self, player: usize) -> Vec<Card> {
+        self.hands[player]
+            .iter()
+            .filter(|card| can_play(card, &self.top, self.suit))
+            .copied()
+            .collect()
+    }
+
+    /// Plays `card` from `player`'s hand. `called` is the suit named when
+    /// playing an Eight and is ignored otherwise.
+    pub fn play(
+        &mut self,
+        player: usize,
+        card: Card,
+        called: Option<Suit>,
+    ) -> Result<(), PlayError> {
+        self.check_turn(player)?;
+        if !self.hands[player].contains(&card) {
+            return Err(PlayError::NotInHand(card));
+        }
+        if !can_play(&card, &self.top, self.suit) {
+            return Err(PlayError::DoesNotMatch(card));
+        }
+        let suit = match (card.rank, called) {
+            (Rank::Eight, Some(suit)) => suit,
+            (Rank::Eight, None) => return Err(PlayError::NoSuitCalled),
+            _ => card.suit,
+        };
+
+        self.hands[player].remove(&card);
+        self.deck.discard(self.top);
+        self.top = card;
+        self.suit = suit;
+        self.passes = 0;
+        if self.hands[player].is_empty() {
+            self.winner = Some(player);
+        } else {
+            self.next_turn();
+        }
+        Ok(())
+    }
+
+    /// Draws one card for `player` and passes the turn
+    pub fn draw(&mut self, player: usize) -> Result<Drawn, PlayError> {
+        self.check_turn(player)?;
+        let drawn = match self.deck.draw_or_recycle(&mut self.rng) {
+            Some(card) => {
+                self.hands[player].add(card);
+                self.passes = 0;
+                Drawn::Card(card)
+            }
+            None => {
+                self.passes += 1;
+                Drawn::Nothing
+            }
+        };
+        self.next_turn();
+        Ok(drawn)
+    }
+
+    fn check_turn(&self, player: usize) -> Result<(), PlayError> {
+        if self.is_over() {
+            Err(PlayError::GameOver)
+        } else if player != self.turn {
+            Err(PlayError::NotYourTurn)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn next_turn(&mut self) {
+        self.turn = (self.turn + 1) % self.players();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deals_only_to_tables_the_deck_can_cover() {
+        for players in [0, 1, MAX_PLAYERS + 1, 52] {
+            assert_eq!(
+                CrazyEights::deal(players, 0).err(),
+                Some(PlayerCountError(players))
+            );
+        }
+        for players in 2..=MAX_PLAYERS {
+            let game = CrazyEights::deal(players, 0).unwrap();
+            assert_eq!(game.players(), players);
+            assert!((0..players).all(|player| game.hand(player).len() == HAND_SIZE));
+            assert_eq!(game.stock_len(), 52 - players * HAND_SIZE - 1);
+        }
+    }
+}
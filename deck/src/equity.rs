@@ -0,0 +1,453 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use crate::card::Card;
+use crate::deck::Deck;
+use crate::hand::Hand;
+use crate::poker::{HandRank, evaluate_unchecked};
+use crate::shuffle::{bounded_index, random_seed, seeded_rng};
+
+const BOARD_SIZE: usize = 5;
+const DECK_SIZE: usize = 52;
+
+/// Samples handed to a worker at a time. Each batch has its own RNG stream,
+/// so results don't depend on how many threads pick them up.
+const SAMPLES_PER_BATCH: u64 = 4096;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EquityError {
+    TooFewPlayers(usize),
+    /// Their hole cards and a full board take more than one deck
+    TooManyPlayers(usize),
+    /// Hold'em players hold exactly two cards
+    WrongHoleCards {
+        player: usize,
+        count: usize,
+    },
+    BoardTooLong(usize),
+    DuplicateCard(Card),
+}
+
+impl fmt::Display for EquityError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EquityError::TooFewPlayers(count) => {
+                write!(f, "need at least 2 players, got {}", count)
+            }
+            EquityError::TooManyPlayers(count) => write!(
+                f,
+                "{} players and a board need {} cards, a deck has {}",
+                count,
+                2 * count + BOARD_SIZE,
+                DECK_SIZE
+            ),
+            EquityError::WrongHoleCards { player, count } => write!(
+                f,
+                "player {} holds {} card(s), expected 2",
+                player + 1,
+                count
+            ),
+            EquityError::BoardTooLong(count) => {
+                write!(
+                    f,
+                    "board has {} cards, at most {} allowed",
+                    count, BOARD_SIZE
+                )
+            }
+            EquityError::DuplicateCard(card) => write!(f, "{} appears more than once", card),
+        }
+    }
+}
+
+impl Error for EquityError {}
+
+/// Texas Hold'em equity of known hands against each other.
+///
+/// Run-outs of the board are sampled from the cards left in the deck, or all
+/// enumerated when there are at most `exact_limit` of them. Work is spread
+/// over `threads` threads; a given seed gives the same answer whatever the
+/// thread count.
+#[derive(Debug, Clone)]
+pub struct EquityCalculator {
+    players: Vec<Hand>,
+    board: Vec<Card>,
+    samples: u64,
+    exact_limit: u64,
+    seed: Option<u64>,
+    threads: usize,
+}
+
+impl EquityCalculator {
+    pub fn new(players: Vec<Hand>) -> Self {
+        EquityCalculator {
+            players,
+            board: vec![],
+            samples: 200_000,
+            // Heads-up preflop (1.7M boards) is still exact
+            exact_limit: 2_000_000,
+            seed: None,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+
+    /// Community cards already dealt (flop, turn, ...)
+    pub fn with_board(mut self, board: &[Card]) -> Self {
+        self.board = board.to_vec();
+        self
+    }
+
+    /// Number of run-outs to sample when not enumerating
+    pub fn with_samples(mut self, samples: u64) -> Self {
+        self.samples = samples.max(1);
+        self
+    }
+
+    /// Enumerate every run-out when there are no more than `limit` of them,
+    /// 0 to always sample
+    pub fn with_exact_limit(mut self, limit: u64) -> Self {
+        self.exact_limit = limit;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    pub fn calculate(&self) -> Result<EquityReport, EquityError> {
+        self.validate()?;
+
+        let known: Vec<&Card> = self.players.iter().flatten().chain(&self.board).collect();
+        let mut deck = Deck::new();
+        deck.cards.retain(|card| !known.contains(&card));
+        let stub = deck.cards;
+
+        let missing = BOARD_SIZE - self.board.len();
+        let run_outs = combinations(stub.len() as u64, missing as u64);
+        let exact = run_outs <= self.exact_limit;
+
+        let tally = if exact {
+            // One batch per choice of the first missing card
+            let batches = if missing == 0 {
+                1
+            } else {
+                stub.len() - missing + 1
+            };
+            self.run_batches(batches, |batch, tally| {
+                let mut board = self.board.clone();
+                if missing == 0 {
+                    self.score(&board, tally);
+                    return;
+                }
+                board.push(stub[batch]);
+                self.enumerate(&stub[batch + 1..], missing - 1, &mut board, tally);
+            })
+        } else {
+            let seed = self.seed.unwrap_or_else(random_seed);
+            let batches = self.samples.div_ceil(SAMPLES_PER_BATCH) as usize;
+            self.run_batches(batches, |batch, tally| {
+                let mut rng = seeded_rng(seed);
+                rng.set_stream(batch as u64);
+                let first = batch as u64 * SAMPLES_PER_BATCH;
+                let count = SAMPLES_PER_BATCH.min(self.samples - first);
+
+                let mut stub = stub.clone();
+                let mut board = self.board.clone();
+                for _ in 0..count {
+                    // Partial Fisher–Yates: only the first `missing` slots
+                    // need to be random
+                    board.truncate(self.board.len());
+                    for slot in 0..missing {
+                        let pick = slot + bounded_index(&mut rng, stub.len() - slot);
+                        stub.swap(slot, pick);
+                        board.push(stub[slot]);
+                    }
+                    self.score(&board, tally);
+                }
+            })
+        };
+
+        Ok(tally.into_report(exact))
+    }
+
+    fn validate(&self) -> Result<(), EquityError> {
+        if self.players.len() < 2 {
+            return Err(EquityError::TooFewPlayers(self.players.len()));
+        }
+        for (player, hand) in self.players.iter().enumerate() {
+            if hand.len() != 2 {
+                return Err(EquityError::WrongHoleCards {
+                    player,
+                    count: hand.len(),
+                });
+            }
+        }
+        if 2 * self.players.len() + BOARD_SIZE > DECK_SIZE {
+            return Err(EquityError::TooManyPlayers(self.players.len()));
+        }
+        if self.board.len() > BOARD_SIZE {
+            return Err(EquityError::BoardTooLong(self.board.len()));
+        }
+
+        let mut seen = Vec::new();
+        let cards = self.players.iter().flatten().chain(&self.board);
+        for card in cards {
+            if seen.contains(card) {
+                return Err(EquityError::DuplicateCard(*card));
+            }
+            seen.push(*card);
+        }
+        Ok(())
+    }
+
+    /// Runs batches `0..batches` across the worker threads and adds up their
+    /// tallies in batch order, so floating point sums come out identical
+    fn run_batches<F>(&self, batches: usize, work: F) -> Tally
+    where
+        F: Fn(usize, &mut Tally) + Sync,
+    {
+        let next = AtomicUsize::new(0);
+        let mut results: Vec<(usize, Tally)> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..self.threads.min(batches))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = vec![];
+                        loop {
+                            let batch = next.fetch_add(1, Ordering::Relaxed);
+                            if batch >= batches {
+                                return done;
+                            }
+                            let mut tally = Tally::new(self.players.len());
+                            work(batch, &mut tally);
+                            done.push((batch, tally));
+                        }
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("equity worker panicked"))
+                .collect()
+        });
+
+        results.sort_by_key(|(batch, _)| *batch);
+        let mut total = Tally::new(self.players.len());
+        for (_, tally) in &results {
+            total.merge(tally);
+        }
+        total
+    }
+
+    /// Every way of adding `missing` cards from `stub` to `board`
+    fn enumerate(&self, stub: &[Card], missing: usize, board: &mut Vec<Card>, tally: &mut Tally) {
+        if missing == 0 {
+            self.score(board, tally);
+            return;
+        }
+        for i in 0..=stub.len() - missing {
+            board.push(stub[i]);
+            self.enumerate(&stub[i + 1..], missing - 1, board, tally);
+            board.pop();
+        }
+    }
+
+    /// Showdown on a complete board
+    fn score(&self, board: &[Card], tally: &mut Tally) {
+        let mut seven = [board[0]; 7];
+        seven[2..].copy_from_slice(board);
+        tally.ranks.clear();
+        for hand in &self.players {
+            seven[..2].copy_from_slice(hand.cards());
+            tally.ranks.push(evaluate_unchecked(&seven));
+        }
+
+        let best = *tally.ranks.iter().max().expect("at least two players");
+        let winners = tally.ranks.iter().filter(|rank| **rank == best).count();
+        for player in 0..self.players.len() {
+            if tally.ranks[player] != best {
+                continue;
+            }
+            if winners == 1 {
+                tally.wins[player] += 1;
+            } else {
+                tally.ties[player] += 1;
+            }
+            tally.shares[player] += 1.0 / winners as f64;
+        }
+        tally.boards += 1;
+    }
+}
+
+/// Running totals for one batch of boards
+#[derive(Debug, Clone)]
+struct Tally {
+    boards: u64,
+    wins: Vec<u64>,
+    ties: Vec<u64>,
+    /// Pot shares: 1 for a win, 1/n for an n-way split
+    shares: Vec<f64>,
+    /// Scratch space for the hands at one showdown
+    ranks: Vec<HandRank>,
+}
+
+impl Tally {
+    fn new(players: usize) -> Self {
+        Tally {
+            boards: 0,
+            wins: vec![0; players],
+            ties: vec![0; players],
+            shares: vec![0.0; players],
+            ranks: Vec::with_capacity(players),
+        }
+    }
+
+    fn merge(&mut self, other: &Tally) {
+        self.boards += other.boards;
+        for player in 0..self.wins.len() {
+            self.wins[player] += other.wins[player];
+            self.ties[player] += other.ties[player];
+            self.shares[player] += other.shares[player];
+        }
+    }
+
+    fn into_report(self, exact: bool) -> EquityReport {
+        let boards = self.boards as f64;
+        let players = (0..self.wins.len())
+            .map(|player| PlayerEquity {
+                win: self.wins[player] as f64 / boards,
+                tie: self.ties[player] as f64 / boards,
+                equity: self.shares[player] / boards,
+            })
+            .collect();
+        EquityReport {
+            players,
+            boards: self.boards,
+            exact,
+        }
+    }
+}
+
+/// Probabilities for one player, between 0 and 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayerEquity {
+    /// Wins the whole pot
+    pub win: f64,
+    /// Splits the pot with at least one other player
+    pub tie: f64,
+    /// Expected share of the pot
+    pub equity: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquityReport {
+    /// In the same order as the hands given to the calculator
+    pub players: Vec<PlayerEquity>,
+    /// Boards played out
+    pub boards: u64,
+    /// Whether every possible board was played out rather than sampled
+    pub exact: bool,
+}
+
+// Player 1: win  81.95%  tie   0.37%  equity  82.14%
+impl fmt::Display for EquityReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, player) in self.players.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(
+                f,
+                "Player {}: win {:>7.2}%  tie {:>7.2}%  equity {:>7.2}%",
+                i + 1,
+                player.win * 100.0,
+                player.tie * 100.0,
+                player.equity * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// n choose k, small enough here (k <= 5) not to overflow
+fn combinations(n: u64, k: u64) -> u64 {
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::parse_cards;
+
+    #[test]
+    fn rejects_more_players_than_the_deck_can_deal_to() {
+        let mut cards = Deck::new().cards.into_iter();
+        let players: Vec<Hand> = (0..24).map(|_| cards.by_ref().take(2).collect()).collect();
+        let calculator = EquityCalculator::new(players).with_samples(10);
+        assert_eq!(
+            calculator.calculate().err(),
+            Some(EquityError::TooManyPlayers(24))
+        );
+
+        let players = calculator.players[..23].to_vec();
+        let report = EquityCalculator::new(players).with_samples(10).calculate();
+        assert!(report.is_ok());
+    }
+
+    fn aces_and_kings() -> Vec<Hand> {
+        vec!["As Ah".parse().unwrap(), "Kd Kc".parse().unwrap()]
+    }
+
+    #[test]
+    fn enumerates_the_turn_and_river_exactly() {
+        let river = parse_cards("2c 7d 9h Js Kh").unwrap();
+        let report = EquityCalculator::new(aces_and_kings())
+            .with_board(&river)
+            .calculate()
+            .unwrap();
+        assert!(report.exact);
+        assert_eq!(report.boards, 1);
+        assert_eq!(report.players[1].equity, 1.0);
+
+        // Only the last two Kings save the Kings on this turn
+        let turn = &river[..4];
+        let report = EquityCalculator::new(aces_and_kings())
+            .with_board(turn)
+            .calculate()
+            .unwrap();
+        assert!(report.exact);
+        assert_eq!(report.boards, 44);
+        assert_eq!(report.players[0].win, 42.0 / 44.0);
+        assert_eq!(report.players[1].win, 2.0 / 44.0);
+        assert_eq!(report.players[0].tie, 0.0);
+    }
+
+    #[test]
+    fn enumerates_aces_against_kings_preflop() {
+        let report = EquityCalculator::new(aces_and_kings()).calculate().unwrap();
+        assert!(report.exact);
+        assert_eq!(report.boards, 1_712_304);
+        assert!((report.players[0].equity - 0.82).abs() < 0.01);
+        assert!((report.players[1].equity - 0.18).abs() < 0.01);
+    }
+
+    #[test]
+    fn a_seed_gives_the_same_samples_on_any_thread_count() {
+        let calculator = EquityCalculator::new(aces_and_kings())
+            .with_exact_limit(0)
+            .with_samples(20_000)
+            .with_seed(11);
+        let alone = calculator.clone().with_threads(1).calculate().unwrap();
+        let shared = calculator.with_threads(4).calculate().unwrap();
+        assert!(!alone.exact);
+        assert_eq!(alone.boards, 20_000);
+        assert_eq!(alone, shared);
+        assert!((alone.players[0].equity - 0.82).abs() < 0.02);
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::card::{Card, Rank, Suit};
+
+/// Cards held by a player, in the order they were received.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand<C = Card> {
+    cards: Vec<C>,
+}
+
+impl<C> Hand<C> {
+    pub fn new() -> Self {
+        Hand { cards: vec![] }
+    }
+
+    pub fn cards(&self) -> &[C] {
+        &self.cards
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    pub fn add(&mut self, card: C) {
+        self.cards.push(card);
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, C> {
+        self.cards.iter()
+    }
+}
+
+impl<C: PartialEq> Hand<C> {
+    /// Removes the first copy of `card` from the hand, if held
+    pub fn remove(&mut self, card: &C) -> Option<C> {
+        let index = self.cards.iter().position(|c| c == card)?;
+        Some(self.cards.remove(index))
+    }
+
+    pub fn contains(&self, card: &C) -> bool {
+        self.cards.contains(card)
+    }
+}
+
+impl<C: Ord> Hand<C> {
+    /// Sorts lowest card first
+    pub fn sort(&mut self) {
+        self.cards.sort();
+    }
+}
+
+impl Hand {
+    pub fn contains_rank(&self, rank: Rank) -> bool {
+        self.cards.iter().any(|card| card.rank == rank)
+    }
+
+    pub fn contains_suit(&self, suit: Suit) -> bool {
+        self.cards.iter().any(|card| card.suit == suit)
+    }
+
+    /// Sorts by suit, then rank (lowest first)
+    pub fn sort_by_suit(&mut self) {
+        self.cards.sort_by_key(|card| (card.suit, card.rank));
+    }
+
+    pub fn group_by_suit(&self) -> BTreeMap<Suit, Vec<Card>> {
+        let mut groups: BTreeMap<Suit, Vec<Card>> = BTreeMap::new();
+        for card in &self.cards {
+            groups.entry(card.suit).or_default().push(*card);
+        }
+        groups
+    }
+
+    pub fn group_by_rank(&self) -> BTreeMap<Rank, Vec<Card>> {
+        let mut groups: BTreeMap<Rank, Vec<Card>> = BTreeMap::new();
+        for card in &self.cards {
+            groups.entry(card.rank).or_default().push(*card);
+        }
+        groups
+    }
+}
+
+impl<C> Default for Hand<C> {
+    fn default() -> Self {
+        Hand::new()
+    }
+}
+
+impl<C> From<Vec<C>> for Hand<C> {
+    fn from(cards: Vec<C>) -> Self {
+        Hand { cards }
+    }
+}
+
+impl<C> From<Hand<C>> for Vec<C> {
+    fn from(hand: Hand<C>) -> Self {
+        hand.cards
+    }
+}
+
+impl<C> FromIterator<C> for Hand<C> {
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Self {
+        Hand {
+            cards: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<C> Extend<C> for Hand<C> {
+    fn extend<I: IntoIterator<Item = C>>(&mut self, iter: I) {
+        self.cards.extend(iter);
+    }
+}
+
+impl<C> IntoIterator for Hand<C> {
+    type Item = C;
+    type IntoIter = std::vec::IntoIter<C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.into_iter()
+    }
+}
+
+impl<'a, C> IntoIterator for &'a Hand<C> {
+    type Item = &'a C;
+    type IntoIter = std::slice::Iter<'a, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cards.iter()
+    }
+}
+
+// "Ace of Hearts, Two of Spades"
+impl<C: fmt::Display> fmt::Display for Hand<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, card) in self.cards.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", card)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand(cards: &str) -> Hand {
+        cards.parse().unwrap()
+    }
+
+    #[test]
+    fn sorts_and_groups_by_rank_or_suit() {
+        let mut cards = hand("Kh 2s Ah 2d 9h");
+        cards.sort();
+        assert_eq!(cards, hand("2d 2s 9h Kh Ah"));
+        cards.sort_by_suit();
+        assert_eq!(cards, hand("2d 9h Kh Ah 2s"));
+
+        let suits = cards.group_by_suit();
+        assert_eq!(suits[&Suit::Hearts], hand("9h Kh Ah").cards());
+        assert!(!suits.contains_key(&Suit::Clubs));
+        assert_eq!(cards.group_by_rank()[&Rank::Two], hand("2d 2s").cards());
+    }
+
+    #[test]
+    fn removes_one_copy_of_a_card() {
+        let mut cards = hand("Qc 7d Qc");
+        let queen = Card::new(Rank::Queen, Suit::Clubs);
+        assert_eq!(cards.remove(&queen), Some(queen));
+        assert_eq!(cards, hand("7d Qc"));
+        assert!(cards.contains(&queen));
+        assert_eq!(cards.remove(&Card::new(Rank::Ace, Suit::Spades)), None);
+        assert!(cards.contains_rank(Rank::Seven));
+        assert!(!cards.contains_suit(Suit::Hearts));
+    }
+}
@@ -0,0 +1,794 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+use crate::card::{Card, Rank, Suit};
+use crate::deck::Deck;
+
+pub const COLUMNS: usize = 7;
+
+/// Aces are low in Klondike: Ace = 1 up to King = 13
+fn pips(card: &Card) -> u8 {
+    match card.rank {
+        Rank::Ace => 1,
+        rank => rank.value(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DrawMode {
+    One,
+    Three,
+}
+
+impl DrawMode {
+    fn count(&self) -> usize {
+        match self {
+            DrawMode::One => 1,
+            DrawMode::Three => 3,
+        }
+    }
+}
+
+/// A single Klondike move. Columns are numbered 0 to 6, left to right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Move {
+    /// Turns cards from the stock onto the waste, or turns the waste back
+    /// over into the stock once the stock is empty
+    Draw,
+    WasteToFoundation,
+    WasteToTableau(usize),
+    TableauToFoundation(usize),
+    /// Moves the top `count` face-up cards of a column onto another
+    TableauToTableau {
+        from: usize,
+        to: usize,
+        count: usize,
+    },
+    FoundationToTableau {
+        suit: Suit,
+        to: usize,
+    },
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Move::Draw => f.write_str("draw"),
+            Move::WasteToFoundation => f.write_str("waste to foundation"),
+            Move::WasteToTableau(to) => write!(f, "waste to column {}", to + 1),
+            Move::TableauToFoundation(from) => write!(f, "column {} to foundation", from + 1),
+            Move::TableauToTableau { from, to, count } => write!(
+                f,
+                "{} card(s) from column {} to column {}",
+                count,
+                from + 1,
+                to + 1
+            ),
+            Move::FoundationToTableau { suit, to } => {
+                write!(f, "{} foundation to column {}", suit, to + 1)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveError {
+    /// The move breaks the rules in the current position
+    Illegal(Move),
+    NothingToUndo,
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoveError::Illegal(mv) => write!(f, "illegal move: {}", mv),
+            MoveError::NothingToUndo => f.write_str("nothing to undo"),
+        }
+    }
+}
+
+impl Error for MoveError {}
+
+/// One tableau pile: hidden cards underneath, a face-up run on top
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Column {
+    pub face_down: Vec<Card>,
+    pub face_up: Vec<Card>,
+}
+
+impl Column {
+    pub fn is_empty(&self) -> bool {
+        self.face_down.is_empty() && self.face_up.is_empty()
+    }
+
+    pub fn top(&self) -> Option<&Card> {
+        self.face_up.last()
+    }
+
+    // Rules leave the newly exposed card face down, but every computer
+    // version turns it over straight away
+    fn flip(&mut self) {
+        if self.face_up.is_empty()
+            && let Some(card) = self.face_down.pop()
+        {
+            self.face_up.push(card);
+        }
+    }
+
+    /// Whether `card` (the bottom of a moved run) can be placed on this column
+    fn accepts(&self, card: &Card) -> bool {
+        match self.top() {
+            None => self.face_down.is_empty() && card.rank == Rank::King,
+            Some(top) => top.suit.is_red() != card.suit.is_red() && pips(top) == pips(card) + 1,
+        }
+    }
+}
+
+/// Everything on the table. Kept separate from the game so it can be
+/// snapshotted for undo and remembered whole by the solver.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Board {
+    tableau: [Column; COLUMNS],
+    /// Indexed by `Suit as usize`
+    foundations: [Vec<Card>; 4],
+    /// Top of the stock is the end
+    stock: Vec<Card>,
+    /// Top of the waste (the playable card) is the end
+    waste: Vec<Card>,
+    /// Times the waste has been turned back into the stock
+    recycles: usize,
+}
+
+/// A game of Klondike solitaire
+#[derive(Debug, Clone)]
+pub struct Klondike {
+    board: Board,
+    draw_mode: DrawMode,
+    /// How many times the waste may be turned over, `None` for no limit
+    max_recycles: Option<usize>,
+    history: Vec<Board>,
+    seed: Option<u64>,
+}
+
+impl Klondike {
+    /// Shuffles a standard deck with `seed` and deals it. The same seed and
+    /// mode always give the same game.
+    pub fn deal(seed: u64, draw_mode: DrawMode) -> Self {
+        let mut deck = Deck::new();
+        deck.shufle_seeded(seed);
+        let mut game = Klondike::from_deck(deck, draw_mode);
+        game.seed = Some(seed);
+        game
+    }
+
+    /// Deals the usual 1-to-7 staircase from the top of `deck`, the rest
+    /// becoming the stock
+    pub fn from_deck(mut deck: Deck, draw_mode: DrawMode) -> Self {
+        let mut tableau: [Column; COLUMNS] = Default::default();
+        for row in 0..COLUMNS {
+            for (col, column) in tableau.iter_mut().enumerate().skip(row) {
+                if let Some(card) = deck.draw_one() {
+                    if col == row {
+                        column.face_up.push(card);
+                    } else {
+                        column.face_down.push(card);
+                    }
+                }
+            }
+        }
+
+        Klondike {
+            board: Board {
+                tableau,
+                foundations: Default::default(),
+                stock: deck.cards,
+                waste: vec![],
+                recycles: 0,
+            },
+            draw_mode,
+            max_recycles: None,
+            history: vec![],
+            seed: None,
+        }
+    }
+
+    /// Limits how many times the waste can be turned back into the stock
+    pub fn with_max_recycles(mut self, max: usize) -> Self {
+        self.max_recycles = Some(max);
+        self
+    }
+
+    /// Seed of the deal. Nothing is random after the deal, so unlike games
+    /// that keep an RNG there is no other position to save.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn draw_mode(&self) -> DrawMode {
+        self.draw_mode
+    }
+
+    pub fn tableau(&self) -> &[Column; COLUMNS] {
+        &self.board.tableau
+    }
+
+    pub fn foundation(&self, suit: Suit) -> &[Card] {
+        &self.board.foundations[suit as usize]
+    }
+
+    pub fn stock_len(&self) -> usize {
+        self.board.stock.len()
+    }
+
+    /// The playable waste card
+    pub fn waste_top(&self) -> Option<&Card> {
+        self.board.waste.last()
+    }
+
+    pub fn moves_made(&self) -> usize {
+        self.history.len()
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.board.foundations.iter().all(|pile| pile.len() == 13)
+    }
+
+    fn can_recycle(&self) -> bool {
+        self.max_recycles
+            .is_none_or(|max| self.board.recycles < max)
+    }
+
+    fn fits_foundation(&self, card: &Card) -> bool {
+        let pile = &self.board.foundations[card.suit as usize];
+        match pile.last() {
+            None => card.rank == Rank::Ace,
+            Some(top) => pips(card) == pips(top) + 1,
+        }
+    }
+
+    pub fn is_legal(&self, mv: Move) -> bool {
+        let board = &self.board;
+        match mv {
+            Move::Draw => {
+                !board.stock.is_empty() || (!board.waste.is_empty() && self.can_recycle())
+            }
+            Move::WasteToFoundation => board
+                .waste
+                .last()
+                .is_some_and(|card| self.fits_foundation(card)),
+            Move::WasteToTableau(to) => {
+                to < COLUMNS
+                    && board
+                        .waste
+                        .last()
+                        .is_some_and(|card| board.tableau[to].accepts(card))
+            }
+            Move::TableauToFoundation(from) => {
+                from < COLUMNS
+                    && board.tableau[from]
+                        .top()
+                        .is_some_and(|card| self.fits_foundation(card))
+            }
+            Move::TableauToTableau { from, to, count } => {
+                if from >= COLUMNS || to >= COLUMNS || from == to || count == 0 {
+                    return false;
+                }
+                let run = &board.tableau[from].face_up;
+                count <= run.len() && board.tableau[to].accepts(&run[run.len() - count])
+            }
+            Move::FoundationToTableau { suit, to } => {
+                to < COLUMNS
+                    && board.foundations[suit as usize]
+                        .last()
+                        .is_some_and(|card| board.tableau[to].accepts(card))
+            }
+        }
+    }
+
+    /// Every legal move in the current position
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let mut moves = vec![];
+
+        moves.push(Move::WasteToFoundation);
+        for from in 0..COLUMNS {
+            moves.push(Move::TableauToFoundation(from));
+        }
+        for from in 0..COLUMNS {
+            for count in 1..=self.board.tableau[from].face_up.len() {
+                for to in 0..COLUMNS {
+                    moves.push(Move::TableauToTableau { from, to, count });
+                }
+            }
+        }
+        for to in 0..COLUMNS {
+            moves.push(Move::WasteToTableau(to));
+        }
+        for suit in Suit::ALL {
+            for to in 0..COLUMNS {
+                moves.push(Move::FoundationToTableau { suit, to });
+            }
+        }
+        moves.push(Move::Draw);
+
+        moves.retain(|mv| self.is_legal(*mv));
+        moves
+    }
+
+    /// Plays `mv` if it is legal. Exposed tableau cards are turned face up.
+    pub fn apply(&mut self, mv: Move) -> Result<(), MoveError> {
+        if !self.is_legal(mv) {
+            return Err(MoveError::Illegal(mv));
+        }
+        self.history.push(self.board.clone());
+        self.play(mv);
+        Ok(())
+    }
+
+    // Carries out a move already known to be legal, without recording it
+    fn play(&mut self, mv: Move) {
+        let board = &mut self.board;
+        match mv {
+            Move::Draw => {
+                if board.stock.is_empty() {
+                    board.stock = board.waste.drain(..).rev().collect();
+                    board.recycles += 1;
+                } else {
+                    for _ in 0..self.draw_mode.count() {
+                        if let Some(card) = board.stock.pop() {
+                            board.waste.push(card);
+                        }
+                    }
+                }
+            }
+            Move::WasteToFoundation => {
+                let card = board.waste.pop().expect("checked by is_legal");
+                board.foundations[card.suit as usize].push(card);
+            }
+            Move::WasteToTableau(to) => {
+                let card = board.waste.pop().expect("checked by is_legal");
+                board.tableau[to].face_up.push(card);
+            }
+            Move::TableauToFoundation(from) => {
+                let card = board.tableau[from]
+                    .face_up
+                    .pop()
+                    .expect("checked by is_legal");
+                board.foundations[card.suit as usize].push(card);
+                board.tableau[from].flip();
+            }
+            Move::TableauToTableau { from, to, count } => {
+                let run = &mut board.tableau[from].face_up;
+                let moved = run.split_off(run.len() - count);
+                board.tableau[to].face_up.extend(moved);
+                board.tableau[from].flip();
+            }
+            Move::FoundationToTableau { suit, to } => {
+                let card = board.foundations[suit as usize]
+                    .pop()
+                    .expect("checked by is_legal");
+                board.tableau[to].face_up.push(card);
+            }
+        }
+    }
+
+    pub fn undo(&mut self) -> Result<(), MoveError> {
+        self.board = self.history.pop().ok_or(MoveError::NothingToUndo)?;
+        Ok(())
+    }
+
+    /// Searches for a winning line from the current position, exploring at
+    /// most `budget` positions.
+    ///
+    /// Depth-first with an explicit stack, never revisiting a position. To
+    /// keep the tree small, cards that are always safe to put up are played
+    /// automatically, and going through the stock is treated as a single
+    /// "draw until this card shows, then play it" step. Splitting a run of
+    /// cards is only tried when it frees a card for the foundation; when that
+    /// skips anything, running out of positions is `Inconclusive` rather than
+    /// `Unwinnable`.
+    pub fn solve(&self, budget: usize) -> SolveOutcome {
+        let mut game = self.clone();
+        game.history.clear();
+
+        let mut line = vec![];
+        game.play_safe_moves(&mut line);
+        if game.is_won() {
+            return SolveOutcome::Solved(line);
+        }
+
+        let mut seen = HashSet::new();
+        seen.insert(game.board.clone());
+        let mut explored = 1;
+        let mut pruned = false;
+        let mut stack = vec![Frame {
+            board: game.board.clone(),
+            line_len: line.len(),
+            steps: game.candidate_steps(&mut pruned),
+        }];
+
+        while let Some(frame) = stack.last_mut() {
+            let Some(step) = frame.steps.pop() else {
+                stack.pop();
+                continue;
+            };
+
+            game.board.clone_from(&frame.board);
+            line.truncate(frame.line_len);
+            for mv in step {
+                game.play(mv);
+                line.push(mv);
+            }
+            game.play_safe_moves(&mut line);
+
+            if game.is_won() {
+                return SolveOutcome::Solved(line);
+            }
+            if !seen.insert(game.board.clone()) {
+                continue;
+            }
+            if explored >= budget {
+                return SolveOutcome::BudgetExhausted { explored };
+            }
+            explored += 1;
+            stack.push(Frame {
+                board: game.board.clone(),
+                line_len: line.len(),
+                steps: game.candidate_steps(&mut pruned),
+            });
+        }
+
+        if pruned {
+            SolveOutcome::Inconclusive { explored }
+        } else {
+            SolveOutcome::Unwinnable { explored }
+        }
+    }
+
+    /// A card can go up for good once both foundations of the other colour
+    /// are high enough that it will never be needed to hold their cards
+    fn is_safe_to_foundation(&self, card: &Card) -> bool {
+        if !self.fits_foundation(card) {
+            return false;
+        }
+        pips(card) <= 2
+            || Suit::ALL
+                .iter()
+                .filter(|suit| suit.is_red() != card.suit.is_red())
+                .all(|suit| self.board.foundations[*suit as usize].len() as u8 >= pips(card) - 1)
+    }
+
+    fn play_safe_moves(&mut self, line: &mut Vec<Move>) {
+        loop {
+            let from_tableau = (0..COLUMNS)
+                .find(|from| {
+                    self.board.tableau[*from]
+                        .top()
+                        .is_some_and(|card| self.is_safe_to_foundation(card))
+                })
+                .map(Move::TableauToFoundation);
+            let from_waste = self
+                .board
+                .waste
+                .last()
+                .filter(|card| self.is_safe_to_foundation(card))
+                .map(|_| Move::WasteToFoundation);
+
+            match from_tableau.or(from_waste) {
+                Some(mv) => {
+                    self.play(mv);
+                    line.push(mv);
+                }
+                None => return,
+            }
+        }
+    }
+
+    /// Steps worth searching from here, best last so they are popped first.
+    ///
+    /// Each step is a list of moves: a single move, or some draws followed by
+    /// playing the card they turn up. Sets `pruned` if a legal move that
+    /// might have helped was left out.
+    fn candidate_steps(&self, pruned: &mut bool) -> Vec<Vec<Move>> {
+        let mut steps: Vec<(u8, usize, Vec<Move>)> = vec![];
+        for mv in self.legal_moves() {
+            if mv == Move::Draw || !self.worth_trying(mv) {
+                continue;
+            }
+            if self.is_long_shot(mv) {
+                *pruned = true;
+                continue;
+            }
+            steps.push((self.priority(mv), 0, vec![mv]));
+        }
+
+        // Walk through the stock until it comes back round, noting every
+        // waste card that could be played along the way. Without plays in
+        // between, the stock and waste sizes alone pin down their contents.
+        let mut scratch = self.clone();
+        scratch.history.clear();
+        let mut visited = HashSet::new();
+        visited.insert((self.board.stock.len(), self.board.waste.len()));
+        let mut turned_up: HashSet<Card> = self.board.waste.last().copied().into_iter().collect();
+        let mut draws = 0;
+        while scratch.is_legal(Move::Draw) {
+            scratch.play(Move::Draw);
+            draws += 1;
+            if !visited.insert((scratch.board.stock.len(), scratch.board.waste.len())) {
+                break;
+            }
+            if let Some(card) = scratch.board.waste.last()
+                && turned_up.insert(*card)
+            {
+                let plays = std::iter::once(Move::WasteToFoundation)
+                    .chain((0..COLUMNS).map(Move::WasteToTableau))
+                    .filter(|mv| scratch.is_legal(*mv));
+                for play in plays {
+                    let mut step = vec![Move::Draw; draws];
+                    step.push(play);
+                    steps.push((self.priority(play), draws, step));
+                }
+            }
+        }
+
+        steps.sort_by_key(|(priority, draws, _)| std::cmp::Reverse((*priority, *draws)));
+        steps.into_iter().map(|(_, _, step)| step).collect()
+    }
+
+    /// Prunes moves that can't make progress: shifting a bare King stack
+    /// from one empty spot to another only swaps two columns around
+    fn worth_trying(&self, mv: Move) -> bool {
+        let tableau = &self.board.tableau;
+        match mv {
+            Move::TableauToTableau { from, to, count } => {
+                let column = &tableau[from];
+                !(count == column.face_up.len()
+                    && column.face_down.is_empty()
+                    && tableau[to].is_empty())
+            }
+            _ => true,
+        }
+    }
+
+    /// Splitting a run rarely helps unless it frees the card beneath it for
+    /// the foundation. Skipping the other splits keeps the search small, at
+    /// the cost of no longer proving a deal unwinnable.
+    fn is_long_shot(&self, mv: Move) -> bool {
+        match mv {
+            Move::TableauToTableau { from, count, .. } => {
+                let column = &self.board.tableau[from].face_up;
+                count < column.len() && !self.fits_foundation(&column[column.len() - count - 1])
+            }
+            _ => false,
+        }
+    }
+
+    /// Lower is tried first
+    fn priority(&self, mv: Move) -> u8 {
+        let tableau = &self.board.tableau;
+        match mv {
+            Move::TableauToFoundation(from) if tableau[from].face_up.len() == 1 => 0,
+            Move::TableauToTableau { from, count, .. }
+                if count == tableau[from].face_up.len() && !tableau[from].face_down.is_empty() =>
+            {
+                1
+            }
+            Move::WasteToFoundation | Move::TableauToFoundation(_) => 2,
+            Move::WasteToTableau(_) => 3,
+            Move::TableauToTableau { .. } => 4,
+            Move::Draw => 5,
+            Move::FoundationToTableau { .. } => 6,
+        }
+    }
+}
+
+/// Position the solver can come back to
+struct Frame {
+    board: Board,
+    line_len: usize,
+    steps: Vec<Vec<Move>>,
+}
+
+/// What `Klondike::solve` found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveOutcome {
+    /// A winning sequence of moves from the position solved
+    Solved(Vec<Move>),
+    /// Every reachable position was explored without finding a win
+    Unwinnable { explored: usize },
+    /// Every position the search tried was explored without finding a win,
+    /// but it skipped moves it judged unlikely to help, so the deal may still
+    /// be winnable
+    Inconclusive { explored: usize },
+    /// Gave up after the search budget; the deal may or may not be winnable
+    BudgetExhausted { explored: usize },
+}
+
+impl fmt::Display for Klondike {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let board = &self.board;
+        write!(f, "Stock: {:>2}  Waste: ", board.stock.len())?;
+        match board.waste.last() {
+            Some(card) => write!(f, "{:<3}", card.short())?,
+            None => f.write_str("-- ")?,
+        }
+        f.write_str("  Foundations:")?;
+        for pile in &board.foundations {
+            match pile.last() {
+                Some(card) => write!(f, " {}", card.short())?,
+                None => f.write_str(" --")?,
+            }
+        }
+        writeln!(f)?;
+
+        let height = board
+            .tableau
+            .iter()
+            .map(|column| column.face_down.len() + column.face_up.len())
+            .max()
+            .unwrap_or(0);
+        for row in 0..height {
+            for column in &board.tableau {
+                let hidden = column.face_down.len();
+                if row < hidden {
+                    f.write_str(" ## ")?;
+                } else if let Some(card) = column.face_up.get(row - hidden) {
+                    write!(f, " {:<3}", card.short())?;
+                } else {
+                    f.write_str("    ")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::parse_cards;
+
+    /// A position from `(face down, face up)` columns and a stock listed
+    /// bottom to top
+    fn position(columns: [(&str, &str); COLUMNS], stock: &str, draw_mode: DrawMode) -> Klondike {
+        let cards = |text: &str| parse_cards(text).unwrap();
+        let mut game = Klondike::from_deck(Deck::from_cards(vec![]), draw_mode);
+        for (column, (down, up)) in game.board.tableau.iter_mut().zip(columns) {
+            column.face_down = cards(down);
+            column.face_up = cards(up);
+        }
+        game.board.stock = cards(stock);
+        game
+    }
+
+    const EMPTY: (&str, &str) = ("", "");
+
+    #[test]
+    fn moves_follow_the_rules() {
+        let game = position(
+            [
+                ("", "Kh"),
+                ("5c", "Qs"),
+                ("", "Qd"),
+                ("", "5h 4s"),
+                EMPTY,
+                EMPTY,
+                EMPTY,
+            ],
+            "Ac",
+            DrawMode::One,
+        );
+        let run = |from, to, count| Move::TableauToTableau { from, to, count };
+
+        assert!(game.is_legal(run(1, 0, 1)));
+        assert!(game.is_legal(run(0, 4, 1)));
+        assert!(game.is_legal(Move::Draw));
+        // Same colour, a non-King into a space, more cards than are face up
+        assert!(!game.is_legal(run(2, 0, 1)));
+        assert!(!game.is_legal(run(1, 4, 1)));
+        assert!(!game.is_legal(run(3, 0, 3)));
+        assert!(!game.is_legal(Move::WasteToFoundation));
+        assert!(!game.is_legal(Move::TableauToFoundation(7)));
+
+        let moves = game.legal_moves();
+        assert!(moves.contains(&run(1, 0, 1)));
+        assert!(moves.iter().all(|mv| game.is_legal(*mv)));
+
+        let mut game = game;
+        let illegal = run(2, 0, 1);
+        assert_eq!(game.apply(illegal), Err(MoveError::Illegal(illegal)));
+        assert_eq!(game.moves_made(), 0);
+    }
+
+    #[test]
+    fn undo_puts_back_the_card_and_turns_it_down_again() {
+        let mut game = position(
+            [("", "Kh"), ("5c", "Qs"), EMPTY, EMPTY, EMPTY, EMPTY, EMPTY],
+            "",
+            DrawMode::One,
+        );
+        assert_eq!(game.undo(), Err(MoveError::NothingToUndo));
+
+        let before = game.tableau().clone();
+        game.apply(Move::TableauToTableau {
+            from: 1,
+            to: 0,
+            count: 1,
+        })
+        .unwrap();
+        assert_eq!(game.tableau()[1].face_up, parse_cards("5c").unwrap());
+        assert!(game.tableau()[1].face_down.is_empty());
+
+        game.undo().unwrap();
+        assert_eq!(game.tableau(), &before);
+        assert_eq!(game.moves_made(), 0);
+    }
+
+    #[test]
+    fn draw_three_cycles_through_the_stock() {
+        let mut game = position([EMPTY; COLUMNS], "2c 3c 4c 5c 6c 7c 8c", DrawMode::Three)
+            .with_max_recycles(1);
+        let waste_tops: Vec<_> = (0..3)
+            .map(|_| {
+                game.apply(Move::Draw).unwrap();
+                game.waste_top().unwrap().short()
+            })
+            .collect();
+        assert_eq!(waste_tops, ["6c", "3c", "2c"]);
+        assert_eq!(game.stock_len(), 0);
+
+        // Turning the waste over gives back the stock in its original order
+        game.apply(Move::Draw).unwrap();
+        assert_eq!(game.stock_len(), 7);
+        assert_eq!(game.waste_top(), None);
+        game.apply(Move::Draw).unwrap();
+        assert_eq!(game.waste_top().unwrap().short(), "6c");
+
+        game.apply(Move::Draw).unwrap();
+        game.apply(Move::Draw).unwrap();
+        assert!(!game.is_legal(Move::Draw));
+    }
+
+    #[test]
+    fn solves_a_stock_in_foundation_order() {
+        let mut stock = vec![];
+        for suit in Suit::ALL {
+            stock.push(Card::new(Rank::Ace, suit));
+            stock.extend(Rank::ALL[..12].iter().map(|&rank| Card::new(rank, suit)));
+        }
+        stock.reverse();
+        let mut game = position([EMPTY; COLUMNS], "", DrawMode::One);
+        game.board.stock = stock;
+
+        let SolveOutcome::Solved(line) = game.solve(1_000) else {
+            panic!("a stock in order wins");
+        };
+        for mv in line {
+            game.apply(mv).unwrap();
+        }
+        assert!(game.is_won());
+    }
+
+    #[test]
+    fn proves_a_dead_deal_unwinnable() {
+        // No Aces showing and nothing that stock or tableau can build on
+        let game = position(
+            [
+                ("", "Kh"),
+                ("As", "Kd"),
+                ("Ac Ad", "Qh"),
+                ("Ah Qs Qc", "Qd"),
+                ("Js Jc Ts Tc", "Jh"),
+                ("9s 9c 2s 3s 4s", "Jd"),
+                ("5s 6s 7s 8s Ks Kc", "Th"),
+            ],
+            "Td 2h 3h 4h 5h 6h 7h 8h 9h 2d 3d 4d 5d 6d 7d 8d 9d 2c 3c 4c 5c 6c 7c 8c",
+            DrawMode::One,
+        );
+        assert!(matches!(
+            game.solve(10_000),
+            SolveOutcome::Unwinnable { .. }
+        ));
+    }
+}
@@ -0,0 +1,32 @@
+pub mod blackjack;
+pub mod builder;
+pub mod card;
+pub mod counting;
+pub mod dealing;
+pub mod deck;
+pub mod eights;
+pub mod equity;
+pub mod hand;
+pub mod klondike;
+pub mod net;
+pub mod notation;
+pub mod poker;
+pub mod render;
+pub mod rummy;
+pub mod save;
+pub mod shoe;
+pub mod shuffle;
+pub mod source;
+pub mod war;
+
+pub use builder::DeckBuilder;
+pub use card::{Card, CustomCard, ExtendedCard, JokerColor, Rank, Suit};
+pub use counting::CardCounting;
+pub use dealing::{DealPlan, TableDeal};
+pub use deck::{DealError, Deck};
+pub use equity::{EquityCalculator, EquityError, EquityReport};
+pub use hand::Hand;
+pub use notation::{CardFormat, ParseCardError};
+pub use save::{LoadError, PileNameError, SavedGame};
+pub use shoe::Shoe;
+pub use source::CardSource;
@@ -1,47 +1,30 @@
-use rand::{rng, seq::SliceRandom};
-
-#[derive(Debug)]
-struct Deck {
-    cards: Vec<String>,
-}
-
-impl Deck {
-    fn new() -> Self {
-        // List of "suits", hearts, spades
-        // List of "values" - ace, etc
-        let suits = ["Hearts", "Spades", "Diamonds"];
-        let values = ["Ace", "Two", "Three"];
-
-        let mut cards = vec![];
-
-        for suit in suits {
-            for value in values {
-                let card = format!("{} of {}", value, suit);
-                cards.push(card);
-            }
-        }
-
-        // No return statements, like Kotlin ?
-        // Implicit return (important no semicolon ;)
-        Deck { cards }
-    }
-
-    fn shufle(&mut self) {
-        let mut rng = rng();
-        self.cards.shuffle(&mut rng);
-    }
-
-    fn deal(&mut self, num_cards: usize) -> Vec<String> {
-        self.cards.split_off(self.cards.len() - num_cards)
-    }
-}
+use deck::{DealPlan, Deck, poker};
 
 fn main() {
     let mut deck = Deck::new();
 
-    deck.shufle();
+    let seed = deck.shufle();
 
     println!("Here's the deck: {:#?}", deck);
+    println!(
+        "Shuffled with seed {} (replay it with Deck::shufle_seeded)",
+        seed
+    );
+
+    // One card at a time around a table of four, five cards each
+    let plan = DealPlan::round_robin(4, 5);
+    match deck.deal_table(&plan) {
+        Ok(deal) => {
+            for (seat, mut hand) in deal.hands.into_iter().enumerate() {
+                hand.sort();
+                match poker::evaluate(hand.cards()) {
+                    Ok(rank) => println!("Seat {}: {} - {}", seat + 1, hand, rank),
+                    Err(err) => println!("Seat {}: {} - {}", seat + 1, hand, err),
+                }
+            }
+        }
+        Err(err) => println!("{}", err),
+    }
 
-    println!("Here's your hand: {:#?}", deck.deal(3));
+    println!("{} cards remaining", deck.remaining());
 }
@@ -0,0 +1,64 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+use crate::net::protocol::{ClientMessage, ServerMessage};
+
+/// One player's connection to a `Server`
+pub struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    seat: usize,
+    names: Vec<String>,
+}
+
+impl Client {
+    /// Connects and joins as `name`. Blocks until the server has seated
+    /// everyone and sent WELCOME.
+    pub fn connect<A: ToSocketAddrs>(addr: A, name: &str) -> io::Result<Self> {
+        let writer = TcpStream::connect(addr)?;
+        let mut client = Client {
+            reader: BufReader::new(writer.try_clone()?),
+            writer,
+            seat: 0,
+            names: vec![],
+        };
+        client.send(&ClientMessage::Hello(name.to_string()))?;
+        loop {
+            match client.recv()? {
+                ServerMessage::Welcome { seat, names } => {
+                    client.seat = seat;
+                    client.names = names;
+                    return Ok(client);
+                }
+                ServerMessage::Rejected(reason) => {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, reason));
+                }
+                _ => continue,
+            }
+        }
+    }
+
+    pub fn seat(&self) -> usize {
+        self.seat
+    }
+
+    /// Every player's name, by seat
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn send(&mut self, message: &ClientMessage) -> io::Result<()> {
+        writeln!(self.writer, "{}", message)
+    }
+
+    /// Waits for the next message. A closed connection is an
+    /// `UnexpectedEof` error.
+    pub fn recv(&mut self) -> io::Result<ServerMessage> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        line.parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
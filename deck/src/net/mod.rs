@@ -0,0 +1,20 @@
+// Crazy Eights between two terminals over TCP.
+//
+// Messages are single lines of text, see `protocol`. A game goes:
+//
+// client -> HELLO Ada
+// server -> WELCOME 0 Ada Bob      (seat, then every name by seat)
+// server -> HAND 2c 8h Qd ...      (private, whenever your hand changes)
+// server -> STATE 7h h 0 37 7 7    (top card, suit to follow, turn, stock, hand sizes)
+// client -> PLAY 7c                or  PLAY 8h s  /  DRAW
+// server -> PLAYED 0 7c            or  REJECTED <reason> to the sender only
+// ...
+// server -> OVER 0
+
+pub mod client;
+pub mod protocol;
+pub mod server;
+
+pub use client::Client;
+pub use protocol::{ClientMessage, ProtocolError, PublicState, ServerMessage};
+pub use server::{GameEnd, Server};
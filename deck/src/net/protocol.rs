@@ -0,0 +1,310 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::card::{Card, Suit};
+use crate::hand::Hand;
+use crate::notation::{CardFormat, ParseCardError, parse_cards};
+
+/// A line that isn't a valid message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProtocolError {
+    Empty,
+    UnknownCommand(String),
+    /// The command is missing an argument or has too many
+    WrongArguments(String),
+    BadCard(ParseCardError),
+    BadNumber(String),
+}
+
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProtocolError::Empty => f.write_str("empty message"),
+            ProtocolError::UnknownCommand(command) => write!(f, "unknown command '{}'", command),
+            ProtocolError::WrongArguments(command) => {
+                write!(f, "wrong arguments for {}", command)
+            }
+            ProtocolError::BadCard(err) => write!(f, "{}", err),
+            ProtocolError::BadNumber(text) => write!(f, "not a number: '{}'", text),
+        }
+    }
+}
+
+impl Error for ProtocolError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ProtocolError::BadCard(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseCardError> for ProtocolError {
+    fn from(err: ParseCardError) -> Self {
+        ProtocolError::BadCard(err)
+    }
+}
+
+/// Sent by a client, one per line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientMessage {
+    /// First message after connecting: `HELLO <name>`
+    Hello(String),
+    /// `PLAY 8h s` plays the Eight of Hearts and calls Spades
+    Play(Card, Option<Suit>),
+    Draw,
+}
+
+/// Everyone can see this about the game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicState {
+    pub top: Card,
+    pub suit: Suit,
+    pub turn: usize,
+    pub stock: usize,
+    /// Number of cards in each player's hand, by seat
+    pub hand_sizes: Vec<usize>,
+}
+
+/// Sent by the server, one per line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerMessage {
+    /// Your seat and the other players' names, by seat
+    Welcome {
+        seat: usize,
+        names: Vec<String>,
+    },
+    /// Your own cards, sent to you alone whenever they change
+    Hand(Hand),
+    State(PublicState),
+    Played {
+        seat: usize,
+        card: Card,
+        suit: Option<Suit>,
+    },
+    /// `seat` drew a card (which card is private) or passed if none was left
+    Drew {
+        seat: usize,
+        passed: bool,
+    },
+    /// Your last message was refused, the game state hasn't changed
+    Rejected(String),
+    /// `None` when nobody could go out
+    GameOver {
+        winner: Option<usize>,
+    },
+    /// A player disconnected, the game is abandoned
+    Left {
+        seat: usize,
+    },
+}
+
+// "HELLO Ada", "PLAY 8h s", "DRAW"
+impl fmt::Display for ClientMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientMessage::Hello(name) => write!(f, "HELLO {}", name),
+            ClientMessage::Play(card, None) => write!(f, "PLAY {}", card.short()),
+            ClientMessage::Play(card, Some(suit)) => {
+                write!(f, "PLAY {} {}", card.short(), suit.letter())
+            }
+            ClientMessage::Draw => f.write_str("DRAW"),
+        }
+    }
+}
+
+impl FromStr for ClientMessage {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (command, rest) = split_command(s)?;
+        let args: Vec<&str> = rest.split_whitespace().collect();
+        match (command, args.as_slice()) {
+            ("HELLO", [_, ..]) => Ok(ClientMessage::Hello(rest.to_string())),
+            ("PLAY", [card]) => Ok(ClientMessage::Play(card.parse()?, None)),
+            ("PLAY", [card, suit]) => Ok(ClientMessage::Play(card.parse()?, Some(suit.parse()?))),
+            ("DRAW", []) => Ok(ClientMessage::Draw),
+            ("HELLO" | "PLAY" | "DRAW", _) => Err(ProtocolError::WrongArguments(command.into())),
+            _ => Err(ProtocolError::UnknownCommand(command.to_string())),
+        }
+    }
+}
+
+// "WELCOME 0 Ada Bob", "HAND 2c 8h", "STATE 7h h 1 30 5 7", "PLAYED 0 8h s",
+// "DREW 1", "PASSED 1", "REJECTED not your turn", "OVER 0", "OVER -", "LEFT 1"
+impl fmt::Display for ServerMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServerMessage::Welcome { seat, names } => {
+                write!(f, "WELCOME {} {}", seat, names.join(" "))
+            }
+            ServerMessage::Hand(hand) => {
+                write!(f, "HAND {}", hand.format(CardFormat::Short))
+            }
+            ServerMessage::State(state) => {
+                write!(
+                    f,
+                    "STATE {} {} {} {}",
+                    state.top.short(),
+                    state.suit.letter(),
+                    state.turn,
+                    state.stock
+                )?;
+                for size in &state.hand_sizes {
+                    write!(f, " {}", size)?;
+                }
+                Ok(())
+            }
+            ServerMessage::Played { seat, card, suit } => {
+                write!(f, "PLAYED {} {}", seat, card.short())?;
+                match suit {
+                    Some(suit) => write!(f, " {}", suit.letter()),
+                    None => Ok(()),
+                }
+            }
+            ServerMessage::Drew {
+                seat,
+                passed: false,
+            } => write!(f, "DREW {}", seat),
+            ServerMessage::Drew { seat, passed: true } => write!(f, "PASSED {}", seat),
+            ServerMessage::Rejected(reason) => write!(f, "REJECTED {}", reason),
+            ServerMessage::GameOver { winner: Some(seat) } => write!(f, "OVER {}", seat),
+            ServerMessage::GameOver { winner: None } => f.write_str("OVER -"),
+            ServerMessage::Left { seat } => write!(f, "LEFT {}", seat),
+        }
+    }
+}
+
+impl FromStr for ServerMessage {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (command, rest) = split_command(s)?;
+        let args: Vec<&str> = rest.split_whitespace().collect();
+        let wrong = || ProtocolError::WrongArguments(command.to_string());
+        let message = match (command, args.as_slice()) {
+            ("WELCOME", [seat, names @ ..]) => ServerMessage::Welcome {
+                seat: number(seat)?,
+                names: names.iter().map(|name| name.to_string()).collect(),
+            },
+            ("HAND", _) => ServerMessage::Hand(Hand::from(parse_cards(rest)?)),
+            ("STATE", [top, suit, turn, stock, sizes @ ..]) => ServerMessage::State(PublicState {
+                top: top.parse()?,
+                suit: suit.parse()?,
+                turn: number(turn)?,
+                stock: number(stock)?,
+                hand_sizes: sizes
+                    .iter()
+                    .map(|size| number(size))
+                    .collect::<Result<_, _>>()?,
+            }),
+            ("PLAYED", [seat, card]) => ServerMessage::Played {
+                seat: number(seat)?,
+                card: card.parse()?,
+                suit: None,
+            },
+            ("PLAYED", [seat, card, suit]) => ServerMessage::Played {
+                seat: number(seat)?,
+                card: card.parse()?,
+                suit: Some(suit.parse()?),
+            },
+            ("DREW", [seat]) => ServerMessage::Drew {
+                seat: number(seat)?,
+                passed: false,
+            },
+            ("PASSED", [seat]) => ServerMessage::Drew {
+                seat: number(seat)?,
+                passed: true,
+            },
+            ("REJECTED", _) => ServerMessage::Rejected(rest.to_string()),
+            ("OVER", ["-"]) => ServerMessage::GameOver { winner: None },
+            ("OVER", [seat]) => ServerMessage::GameOver {
+                winner: Some(number(seat)?),
+            },
+            ("LEFT", [seat]) => ServerMessage::Left {
+                seat: number(seat)?,
+            },
+            ("WELCOME" | "STATE" | "PLAYED" | "DREW" | "PASSED" | "OVER" | "LEFT", _) => {
+                return Err(wrong());
+            }
+            _ => return Err(ProtocolError::UnknownCommand(command.to_string())),
+        };
+        Ok(message)
+    }
+}
+
+/// "PLAY 8h s" -> ("PLAY", "8h s")
+fn split_command(line: &str) -> Result<(&str, &str), ProtocolError> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Err(ProtocolError::Empty);
+    }
+    let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+    Ok((command, rest.trim()))
+}
+
+fn number(text: &str) -> Result<usize, ProtocolError> {
+    text.parse()
+        .map_err(|_| ProtocolError::BadNumber(text.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::Rank;
+
+    fn round_trip<M>(message: M)
+    where
+        M: fmt::Display + FromStr<Err = ProtocolError> + PartialEq + fmt::Debug,
+    {
+        let line = message.to_string();
+        assert_eq!(line.parse::<M>(), Ok(message), "{:?}", line);
+    }
+
+    #[test]
+    fn every_message_survives_a_round_trip() {
+        let eight = Card::new(Rank::Eight, Suit::Hearts);
+
+        round_trip(ClientMessage::Hello("Ada_Lovelace".into()));
+        round_trip(ClientMessage::Play(eight, None));
+        round_trip(ClientMessage::Play(eight, Some(Suit::Spades)));
+        round_trip(ClientMessage::Draw);
+
+        round_trip(ServerMessage::Welcome {
+            seat: 1,
+            names: vec!["Ada".into(), "Bob".into()],
+        });
+        round_trip(ServerMessage::Hand(parse_cards("2c 8h Ks").unwrap().into()));
+        round_trip(ServerMessage::Hand(Hand::new()));
+        round_trip(ServerMessage::State(PublicState {
+            top: eight,
+            suit: Suit::Clubs,
+            turn: 1,
+            stock: 30,
+            hand_sizes: vec![5, 7],
+        }));
+        round_trip(ServerMessage::Played {
+            seat: 0,
+            card: eight,
+            suit: Some(Suit::Diamonds),
+        });
+        round_trip(ServerMessage::Played {
+            seat: 1,
+            card: Card::new(Rank::Seven, Suit::Clubs),
+            suit: None,
+        });
+        round_trip(ServerMessage::Drew {
+            seat: 1,
+            passed: false,
+        });
+        round_trip(ServerMessage::Drew {
+            seat: 0,
+            passed: true,
+        });
+        round_trip(ServerMessage::Rejected("not your turn".into()));
+        round_trip(ServerMessage::GameOver { winner: Some(0) });
+        round_trip(ServerMessage::GameOver { winner: None });
+        round_trip(ServerMessage::Left { seat: 1 });
+    }
+}
@@ -0,0 +1,311 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use crate::card::Rank;
+use crate::eights::{CrazyEights, Drawn};
+use crate::net::protocol::{ClientMessage, ProtocolError, PublicState, ServerMessage};
+
+pub const PLAYERS: usize = 2;
+
+/// How a hosted game finished
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameEnd {
+    Winner(usize),
+    /// Nobody could play or draw any more
+    Stalemate,
+    /// The player in this seat disconnected
+    Abandoned(usize),
+}
+
+/// A line from a player, or `None` once their connection closed
+type Incoming = (usize, Option<Result<ClientMessage, ProtocolError>>);
+
+/// Hosts Crazy Eights for two players over TCP.
+///
+/// The server owns the deck and every hand. Each player only ever receives
+/// their own cards plus the public state, and every move is checked against
+/// the rules before it is applied.
+pub struct Server {
+    listener: TcpListener,
+}
+
+impl Server {
+    /// Listens on `addr`. Port 0 picks a free port, see `local_addr`.
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        Ok(Server {
+            listener: TcpListener::bind(addr)?,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Waits for two players to join, then plays one game dealt from `seed`
+    pub fn host_game(&self, seed: u64) -> io::Result<GameEnd> {
+        let (sender, receiver) = mpsc::channel();
+        let mut names = vec![];
+        let mut writers = vec![];
+        for seat in 0..PLAYERS {
+            let (name, writer) = self.join(seat, sender.clone())?;
+            names.push(name);
+            writers.push(writer);
+        }
+
+        let mut table = Table {
+            game: CrazyEights::deal(PLAYERS, seed).expect("PLAYERS is a legal table size"),
+            writers,
+        };
+        let end = table.play(&names, &receiver);
+        for writer in &table.writers {
+            // Unblocks the reader threads
+            let _ = writer.shutdown(Shutdown::Both);
+        }
+        Ok(end)
+    }
+
+    /// Accepts one connection and waits for its HELLO. Further lines are
+    /// forwarded to `sender` from a thread of their own.
+    fn join(&self, seat: usize, sender: Sender<Incoming>) -> io::Result<(String, TcpStream)> {
+        loop {
+            let (stream, _) = self.listener.accept()?;
+            let mut writer = stream.try_clone()?;
+            let mut lines = BufReader::new(stream).lines();
+
+            let name = loop {
+                let Some(Ok(line)) = lines.next() else {
+                    break None;
+                };
+                match line.parse() {
+                    Ok(ClientMessage::Hello(name)) => match seat_name(&name) {
+                        Some(name) => break Some(name),
+                        None => send(
+                            &mut writer,
+                            &ServerMessage::Rejected("a name can't be blank".into()),
+                        ),
+                    },
+                    Ok(_) => send(
+                        &mut writer,
+                        &ServerMessage::Rejected("say HELLO first".into()),
+                    ),
+                    Err(err) => send(&mut writer, &ServerMessage::Rejected(err.to_string())),
+                }
+            };
+            // Gone before saying hello: wait for someone else
+            let Some(name) = name else { continue };
+
+            thread::spawn(move || {
+                for line in lines {
+                    let Ok(line) = line else { break };
+                    if sender.send((seat, Some(line.parse()))).is_err() {
+                        return;
+                    }
+                }
+                let _ = sender.send((seat, None));
+            });
+            return Ok((name, writer));
+        }
+    }
+}
+
+/// Names are sent space separated in WELCOME, so any run of whitespace in
+/// one becomes a single underscore. `None` if nothing is left.
+fn seat_name(name: &str) -> Option<String> {
+    let name = name.split_whitespace().collect::<Vec<_>>().join("_");
+    if name.is_empty() { None } else { Some(name) }
+}
+
+struct Table {
+    game: CrazyEights,
+    writers: Vec<TcpStream>,
+}
+
+impl Table {
+    fn play(&mut self, names: &[String], receiver: &Receiver<Incoming>) -> GameEnd {
+        for seat in 0..PLAYERS {
+            let welcome = ServerMessage::Welcome {
+                seat,
+                names: names.to_vec(),
+            };
+            send(&mut self.writers[seat], &welcome);
+            self.send_hand(seat);
+        }
+        self.broadcast(&ServerMessage::State(self.public_state()));
+
+        while !self.game.is_over() {
+            let Ok((seat, message)) = receiver.recv() else {
+                break;
+            };
+            let message = match message {
+                None => {
+                    self.broadcast(&ServerMessage::Left { seat });
+                    return GameEnd::Abandoned(seat);
+                }
+                Some(Err(err)) => {
+                    self.reject(seat, err.to_string());
+                    continue;
+                }
+                Some(Ok(message)) => message,
+            };
+
+            let result = match message {
+                ClientMessage::Hello(_) => {
+                    self.reject(seat, "already joined".to_string());
+                    continue;
+                }
+                ClientMessage::Play(card, called) => {
+                    self.game
+                        .play(seat, card, called)
+                        .map(|()| ServerMessage::Played {
+                            seat,
+                            card,
+                            suit: called.filter(|_| card.rank == Rank::Eight),
+                        })
+                }
+                ClientMessage::Draw => self.game.draw(seat).map(|drawn| ServerMessage::Drew {
+                    seat,
+                    passed: drawn == Drawn::Nothing,
+                }),
+            };
+            match result {
+                Ok(event) => {
+                    self.broadcast(&event);
+                    self.send_hand(seat);
+                    // Nobody is asked to move once the game is over
+                    if !self.game.is_over() {
+                        self.broadcast(&ServerMessage::State(self.public_state()));
+                    }
+                }
+                Err(err) => self.reject(seat, err.to_string()),
+            }
+        }
+
+        let winner = self.game.winner();
+        self.broadcast(&ServerMessage::GameOver { winner });
+        match winner {
+            Some(seat) => GameEnd::Winner(seat),
+            None => GameEnd::Stalemate,
+        }
+    }
+
+    fn public_state(&self) -> PublicState {
+        PublicState {
+            top: self.game.top(),
+            suit: self.game.suit(),
+            turn: self.game.turn(),
+            stock: self.game.stock_len(),
+            hand_sizes: (0..PLAYERS)
+                .map(|seat| self.game.hand(seat).len())
+                .collect(),
+        }
+    }
+
+    fn send_hand(&mut self, seat: usize) {
+        let hand = ServerMessage::Hand(self.game.hand(seat).clone());
+        send(&mut self.writers[seat], &hand);
+    }
+
+    fn reject(&mut self, seat: usize, reason: String) {
+        send(&mut self.writers[seat], &ServerMessage::Rejected(reason));
+    }
+
+    fn broadcast(&mut self, message: &ServerMessage) {
+        for writer in &mut self.writers {
+            send(writer, message);
+        }
+    }
+}
+
+/// Best effort: a dead connection is noticed by its reader thread, which
+/// reports the player as gone
+fn send(writer: &mut TcpStream, message: &ServerMessage) {
+    let _ = writeln!(writer, "{}", message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::Card;
+    use crate::eights::{HAND_SIZE, can_play};
+    use crate::hand::Hand;
+    use crate::net::client::Client;
+    use crate::net::protocol::PublicState;
+
+    /// Plays the first legal card and calls its suit on an Eight. Returns
+    /// every message it got after WELCOME.
+    fn play_bot(addr: SocketAddr, name: &str) -> io::Result<(usize, Vec<ServerMessage>)> {
+        let mut client = Client::connect(addr, name)?;
+        let mut hand = Hand::new();
+        let mut seen = vec![];
+        loop {
+            let message = client.recv()?;
+            seen.push(message.clone());
+            match message {
+                ServerMessage::Hand(cards) => hand = cards,
+                ServerMessage::State(PublicState {
+                    top, suit, turn, ..
+                }) if turn == client.seat() => {
+                    let choice: Option<Card> =
+                        hand.iter().find(|card| can_play(card, &top, suit)).copied();
+                    let message = match choice {
+                        Some(card) => ClientMessage::Play(card, Some(card.suit)),
+                        None => ClientMessage::Draw,
+                    };
+                    client.send(&message)?;
+                }
+                ServerMessage::GameOver { .. } => return Ok((client.seat(), seen)),
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn names_lose_their_whitespace() {
+        assert_eq!(seat_name("Ada"), Some("Ada".to_string()));
+        assert_eq!(
+            seat_name(" Ada \t Lovelace\n"),
+            Some("Ada_Lovelace".to_string())
+        );
+        assert_eq!(seat_name(" \t "), None);
+        assert_eq!(seat_name(""), None);
+    }
+
+    #[test]
+    fn two_clients_play_a_game_over_localhost() {
+        let server = Server::bind("127.0.0.1:0").unwrap();
+        let addr = server.local_addr().unwrap();
+        let bots: Vec<_> = ["North", "South"]
+            .into_iter()
+            .map(|name| thread::spawn(move || play_bot(addr, name)))
+            .collect();
+
+        let end = server.host_game(7).unwrap();
+        let winner = match end {
+            GameEnd::Winner(seat) => Some(seat),
+            GameEnd::Stalemate => None,
+            GameEnd::Abandoned(seat) => panic!("seat {} left", seat),
+        };
+
+        let mut seats = vec![];
+        for bot in bots {
+            let (seat, seen) = bot.join().unwrap().unwrap();
+            seats.push(seat);
+            assert!(
+                !seen
+                    .iter()
+                    .any(|message| matches!(message, ServerMessage::Rejected(_))),
+                "a legal move was refused"
+            );
+            assert_eq!(seen.last(), Some(&ServerMessage::GameOver { winner }));
+            // Each player's own cards come straight after WELCOME
+            let Some(ServerMessage::Hand(first)) = seen.first() else {
+                panic!("no hand dealt");
+            };
+            assert_eq!(first.len(), HAND_SIZE);
+        }
+        seats.sort();
+        assert_eq!(seats, [0, 1]);
+    }
+}
@@ -0,0 +1,406 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::card::{Card, ExtendedCard, JokerColor, Rank, Suit};
+use crate::hand::Hand;
+
+/// How to write a card out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardFormat {
+    /// "Ace of Spades"
+    Long,
+    /// "As", "Td" - rank letter then lowercase suit letter
+    Short,
+    /// "A♠", "10♦"
+    Symbol,
+    /// The single Unicode playing card character, "🂡"
+    Unicode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+    Empty,
+    UnknownRank(String),
+    UnknownSuit(String),
+    /// Not a card in any of the supported notations
+    Malformed(String),
+}
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseCardError::Empty => f.write_str("empty card"),
+            ParseCardError::UnknownRank(rank) => {
+                write!(f, "unknown rank '{}', expected 2-10, T, J, Q, K or A", rank)
+            }
+            ParseCardError::UnknownSuit(suit) => {
+                write!(f, "unknown suit '{}', expected s, h, d, c or ♠ ♥ ♦ ♣", suit)
+            }
+            ParseCardError::Malformed(card) => write!(f, "'{}' is not a card", card),
+        }
+    }
+}
+
+impl Error for ParseCardError {}
+
+// Unicode "Playing Cards" block: one row of 16 code points per suit,
+// Ace at offset 1, Jack at 0xB, Knight at 0xC, Queen at 0xD, King at 0xE
+const UNICODE_SPADES: u32 = 0x1F0A0;
+const UNICODE_RED_JOKER: u32 = 0x1F0BF;
+const UNICODE_BLACK_JOKER: u32 = 0x1F0CF;
+const UNICODE_FOOL: u32 = 0x1F0E0;
+
+impl Suit {
+    /// Lowercase letter used in short notation
+    pub fn letter(&self) -> char {
+        match self {
+            Suit::Clubs => 'c',
+            Suit::Diamonds => 'd',
+            Suit::Hearts => 'h',
+            Suit::Spades => 's',
+        }
+    }
+
+    pub fn symbol(&self) -> char {
+        match self {
+            Suit::Clubs => '♣',
+            Suit::Diamonds => '♦',
+            Suit::Hearts => '♥',
+            Suit::Spades => '♠',
+        }
+    }
+
+    fn unicode_row(&self) -> u32 {
+        match self {
+            Suit::Spades => UNICODE_SPADES,
+            Suit::Hearts => UNICODE_SPADES + 0x10,
+            Suit::Diamonds => UNICODE_SPADES + 0x20,
+            Suit::Clubs => UNICODE_SPADES + 0x30,
+        }
+    }
+
+    fn from_unicode_row(row: u32) -> Option<Suit> {
+        Suit::ALL.into_iter().find(|suit| suit.unicode_row() == row)
+    }
+}
+
+impl FromStr for Suit {
+    type Err = ParseCardError;
+
+    /// Accepts letters (`s`, `H`), symbols (`♠`, `♡`) and names (`spades`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" => Err(ParseCardError::Empty),
+            "c" | "♣" | "♧" | "clubs" => Ok(Suit::Clubs),
+            "d" | "♦" | "♢" | "diamonds" => Ok(Suit::Diamonds),
+            "h" | "♥" | "♡" | "hearts" => Ok(Suit::Hearts),
+            "s" | "♠" | "♤" | "spades" => Ok(Suit::Spades),
+            _ => Err(ParseCardError::UnknownSuit(s.trim().to_string())),
+        }
+    }
+}
+
+impl Rank {
+    /// Single character used in short notation, `T` for Ten
+    pub fn letter(&self) -> char {
+        match self {
+            Rank::Ten => 'T',
+            Rank::Jack => 'J',
+            Rank::Queen => 'Q',
+            Rank::King => 'K',
+            Rank::Ace => 'A',
+            rank => char::from(b'0' + rank.value()),
+        }
+    }
+
+    fn unicode_offset(&self) -> u32 {
+        match self {
+            Rank::Ace => 1,
+            Rank::Jack => 0xB,
+            Rank::Queen => 0xD,
+            Rank::King => 0xE,
+            rank => rank.value() as u32,
+        }
+    }
+
+    fn from_unicode_offset(offset: u32) -> Option<Rank> {
+        Rank::ALL
+            .into_iter()
+            .find(|rank| rank.unicode_offset() == offset)
+    }
+}
+
+impl FromStr for Rank {
+    type Err = ParseCardError;
+
+    /// Accepts `2`-`10`, `T`, `J`, `Q`, `K`, `A` (any case) and names (`ace`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        let rank = match lower.as_str() {
+            "" => return Err(ParseCardError::Empty),
+            "t" | "10" => Rank::Ten,
+            "j" => Rank::Jack,
+            "q" => Rank::Queen,
+            "k" => Rank::King,
+            "a" | "1" => Rank::Ace,
+            other => match other.parse::<u8>() {
+                Ok(value @ 2..=9) => Rank::ALL[value as usize - 2],
+                _ => Rank::ALL
+                    .into_iter()
+                    .find(|rank| rank.name().to_lowercase() == other)
+                    .ok_or_else(|| ParseCardError::UnknownRank(s.trim().to_string()))?,
+            },
+        };
+        Ok(rank)
+    }
+}
+
+impl Card {
+    /// "As", "Td"
+    pub fn short(&self) -> String {
+        format!("{}{}", self.rank.letter(), self.suit.letter())
+    }
+
+    /// "A♠", "10♦"
+    pub fn symbol(&self) -> String {
+        match self.rank {
+            Rank::Ten => format!("10{}", self.suit.symbol()),
+            rank => format!("{}{}", rank.letter(), self.suit.symbol()),
+        }
+    }
+
+    /// The card's character in the Unicode Playing Cards block
+    pub fn unicode(&self) -> char {
+        let code = self.suit.unicode_row() + self.rank.unicode_offset();
+        char::from_u32(code).expect("playing card code points are valid chars")
+    }
+
+    fn from_unicode(c: char) -> Option<Card> {
+        let code = c as u32;
+        let suit = Suit::from_unicode_row(code & !0xF)?;
+        let rank = Rank::from_unicode_offset(code & 0xF)?;
+        Some(Card::new(rank, suit))
+    }
+
+    pub fn format(&self, format: CardFormat) -> String {
+        match format {
+            CardFormat::Long => self.to_string(),
+            CardFormat::Short => self.short(),
+            CardFormat::Symbol => self.symbol(),
+            CardFormat::Unicode => self.unicode().to_string(),
+        }
+    }
+}
+
+/// Splits "Ten of Hearts" / "10h" / "T♥" into rank and suit parts
+fn split_card(s: &str) -> Result<(&str, &str), ParseCardError> {
+    if let Some((rank, suit)) = s.split_once(" of ") {
+        return Ok((rank, suit));
+    }
+    let (at, _) = s.char_indices().last().ok_or(ParseCardError::Empty)?;
+    if at == 0 {
+        return Err(ParseCardError::Malformed(s.to_string()));
+    }
+    Ok(s.split_at(at))
+}
+
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    /// Parses any of the `CardFormat`s: "Ace of Spades", "AS", "as", "10h",
+    /// "Td", "A♠" or "🂡"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Card::from_unicode(c).ok_or_else(|| ParseCardError::Malformed(s.to_string()));
+        }
+
+        let (rank, suit) = split_card(s)?;
+        Ok(Card::new(rank.parse()?, suit.parse()?))
+    }
+}
+
+/// Parses a list of cards separated by spaces or commas, e.g. "As Kd 10h"
+pub fn parse_cards(s: &str) -> Result<Vec<Card>, ParseCardError> {
+    // Long names contain spaces, so only split those on commas
+    if s.contains(" of ") {
+        return s
+            .split(',')
+            .filter(|part| !part.trim().is_empty())
+            .map(str::parse)
+            .collect();
+    }
+    s.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|part| !part.is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+impl FromStr for Hand {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_cards(s).map(Hand::from)
+    }
+}
+
+impl Hand {
+    /// Writes every card in `format`, separated by spaces, or by commas for
+    /// the long names so they parse back
+    pub fn format(&self, format: CardFormat) -> String {
+        let cards: Vec<String> = self.iter().map(|card| card.format(format)).collect();
+        match format {
+            CardFormat::Long => cards.join(", "),
+            _ => cards.join(" "),
+        }
+    }
+}
+
+impl ExtendedCard {
+    /// Short codes: standard cards as `Card::short`, "Cs" for the Knight
+    /// (Cavalier) of Spades, "T1" to "T21" for trumps, "FL" for the Fool and
+    /// "BJ" / "RJ" for jokers
+    pub fn short(&self) -> String {
+        match self {
+            ExtendedCard::Standard(card) => card.short(),
+            ExtendedCard::Knight(suit) => format!("C{}", suit.letter()),
+            ExtendedCard::Trump(number) => format!("T{}", number),
+            ExtendedCard::Fool => String::from("FL"),
+            ExtendedCard::Joker(JokerColor::Black) => String::from("BJ"),
+            ExtendedCard::Joker(JokerColor::Red) => String::from("RJ"),
+        }
+    }
+
+    pub fn symbol(&self) -> String {
+        match self {
+            ExtendedCard::Standard(card) => card.symbol(),
+            ExtendedCard::Knight(suit) => format!("C{}", suit.symbol()),
+            other => other.short(),
+        }
+    }
+
+    pub fn unicode(&self) -> char {
+        let code = match self {
+            ExtendedCard::Standard(card) => return card.unicode(),
+            ExtendedCard::Knight(suit) => suit.unicode_row() + 0xC,
+            ExtendedCard::Trump(number) => UNICODE_FOOL + *number as u32,
+            ExtendedCard::Fool => UNICODE_FOOL,
+            ExtendedCard::Joker(JokerColor::Black) => UNICODE_BLACK_JOKER,
+            ExtendedCard::Joker(JokerColor::Red) => UNICODE_RED_JOKER,
+        };
+        char::from_u32(code).expect("playing card code points are valid chars")
+    }
+
+    pub fn format(&self, format: CardFormat) -> String {
+        match format {
+            CardFormat::Long => self.to_string(),
+            CardFormat::Short => self.short(),
+            CardFormat::Symbol => self.symbol(),
+            CardFormat::Unicode => self.unicode().to_string(),
+        }
+    }
+
+    fn from_unicode(c: char) -> Option<ExtendedCard> {
+        let code = c as u32;
+        match code {
+            UNICODE_RED_JOKER => Some(ExtendedCard::Joker(JokerColor::Red)),
+            UNICODE_BLACK_JOKER => Some(ExtendedCard::Joker(JokerColor::Black)),
+            UNICODE_FOOL => Some(ExtendedCard::Fool),
+            _ if (UNICODE_FOOL + 1..=UNICODE_FOOL + 21).contains(&code) => {
+                Some(ExtendedCard::Trump((code - UNICODE_FOOL) as u8))
+            }
+            _ if code & 0xF == 0xC => Suit::from_unicode_row(code & !0xF).map(ExtendedCard::Knight),
+            _ => Card::from_unicode(c).map(ExtendedCard::Standard),
+        }
+    }
+}
+
+impl FromStr for ExtendedCard {
+    type Err = ParseCardError;
+
+    /// Parses the long names, the short codes of `ExtendedCard::short`, or
+    /// the Unicode character of any card
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return ExtendedCard::from_unicode(c)
+                .ok_or_else(|| ParseCardError::Malformed(s.to_string()));
+        }
+
+        let upper = s.to_uppercase();
+        match upper.as_str() {
+            "BJ" | "BLACK JOKER" => return Ok(ExtendedCard::Joker(JokerColor::Black)),
+            "RJ" | "RED JOKER" => return Ok(ExtendedCard::Joker(JokerColor::Red)),
+            "FL" | "THE FOOL" | "FOOL" => return Ok(ExtendedCard::Fool),
+            _ => {}
+        }
+
+        let trump = upper
+            .strip_prefix("TRUMP ")
+            .or_else(|| upper.strip_prefix('T'));
+        if let Some(Ok(number @ 1..=21)) = trump.map(|number| number.trim().parse::<u8>()) {
+            return Ok(ExtendedCard::Trump(number));
+        }
+
+        let (rank, suit) = split_card(s)?;
+        if matches!(rank.trim().to_lowercase().as_str(), "c" | "knight") {
+            return Ok(ExtendedCard::Knight(suit.parse()?));
+        }
+        Ok(ExtendedCard::Standard(Card::new(
+            rank.parse()?,
+            suit.parse()?,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::DeckBuilder;
+    use crate::deck::Deck;
+
+    const FORMATS: [CardFormat; 4] = [
+        CardFormat::Long,
+        CardFormat::Short,
+        CardFormat::Symbol,
+        CardFormat::Unicode,
+    ];
+
+    #[test]
+    fn every_card_parses_back_from_every_format() {
+        for card in Deck::new().cards {
+            assert_eq!(card.to_string().parse(), Ok(card));
+            for format in FORMATS {
+                assert_eq!(card.format(format).parse(), Ok(card), "{:?}", format);
+            }
+        }
+    }
+
+    #[test]
+    fn extended_cards_parse_back_from_every_format() {
+        let jokers = DeckBuilder::new().build_with_jokers(2).cards;
+        for card in DeckBuilder::tarot().cards.into_iter().chain(jokers) {
+            assert_eq!(card.to_string().parse(), Ok(card));
+            for format in FORMATS {
+                assert_eq!(card.format(format).parse(), Ok(card), "{:?}", format);
+            }
+        }
+    }
+
+    #[test]
+    fn hands_parse_back_from_every_format() {
+        let hand = Hand::from(Deck::new().cards);
+        assert_eq!(hand.to_string().parse(), Ok(hand.clone()));
+        for format in FORMATS {
+            assert_eq!(
+                hand.format(format).parse(),
+                Ok(hand.clone()),
+                "{:?}",
+                format
+            );
+        }
+    }
+}
@@ -0,0 +1,401 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+
+use crate::card::{Card, Rank};
+
+/// Poker hand categories, weakest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HandCategory {
+    HighCard,
+    Pair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl HandCategory {
+    const ALL: [HandCategory; 9] = [
+        HandCategory::HighCard,
+        HandCategory::Pair,
+        HandCategory::TwoPair,
+        HandCategory::ThreeOfAKind,
+        HandCategory::Straight,
+        HandCategory::Flush,
+        HandCategory::FullHouse,
+        HandCategory::FourOfAKind,
+        HandCategory::StraightFlush,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HandCategory::HighCard => "High Card",
+            HandCategory::Pair => "Pair",
+            HandCategory::TwoPair => "Two Pair",
+            HandCategory::ThreeOfAKind => "Three of a Kind",
+            HandCategory::Straight => "Straight",
+            HandCategory::Flush => "Flush",
+            HandCategory::FullHouse => "Full House",
+            HandCategory::FourOfAKind => "Four of a Kind",
+            HandCategory::StraightFlush => "Straight Flush",
+        }
+    }
+}
+
+impl fmt::Display for HandCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Strength of a poker hand, comparable with `<`, `>` and `==`.
+///
+/// Packed into a single `u32` so comparing two hands is one integer compare:
+/// the category sits in bits 20-23, then up to five tie-break ranks of 4 bits
+/// each, most significant first (pair rank before kickers, etc.).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HandRank(u32);
+
+impl HandRank {
+    fn new(category: HandCategory, ranks: &[u8]) -> Self {
+        let mut value = (category as u32) << 20;
+        for (i, rank) in ranks.iter().take(5).enumerate() {
+            value |= (*rank as u32) << (16 - 4 * i);
+        }
+        HandRank(value)
+    }
+
+    pub fn category(&self) -> HandCategory {
+        HandCategory::ALL[(self.0 >> 20) as usize]
+    }
+
+    /// Ranks used to break ties, in the order they are compared
+    pub fn tie_breakers(&self) -> Vec<Rank> {
+        let count = match self.category() {
+            HandCategory::StraightFlush | HandCategory::Straight => 1,
+            HandCategory::FourOfAKind | HandCategory::FullHouse => 2,
+            HandCategory::TwoPair | HandCategory::ThreeOfAKind => 3,
+            HandCategory::Pair => 4,
+            HandCategory::Flush | HandCategory::HighCard => 5,
+        };
+        (0..count)
+            .map(|i| Rank::ALL[((self.0 >> (16 - 4 * i)) & 0xF) as usize])
+            .collect()
+    }
+
+    /// Raw packed value, handy as a sort key or lookup index
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+// "Full House, Kings over Fives"
+impl fmt::Display for HandRank {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ranks = self.tie_breakers();
+        match self.category() {
+            HandCategory::StraightFlush if ranks[0] == Rank::Ace => f.write_str("Royal Flush"),
+            HandCategory::StraightFlush | HandCategory::Straight | HandCategory::Flush => {
+                write!(f, "{}, {} high", self.category(), ranks[0])
+            }
+            HandCategory::FourOfAKind => write!(f, "Four {}", plural(ranks[0])),
+            HandCategory::FullHouse => write!(
+                f,
+                "Full House, {} over {}",
+                plural(ranks[0]),
+                plural(ranks[1])
+            ),
+            HandCategory::ThreeOfAKind => write!(f, "Three {}", plural(ranks[0])),
+            HandCategory::TwoPair => {
+                write!(f, "Two Pair, {} and {}", plural(ranks[0]), plural(ranks[1]))
+            }
+            HandCategory::Pair => write!(f, "Pair of {}", plural(ranks[0])),
+            HandCategory::HighCard => write!(f, "High Card, {}", ranks[0]),
+        }
+    }
+}
+
+fn plural(rank: Rank) -> &'static str {
+    match rank {
+        Rank::Two => "Twos",
+        Rank::Three => "Threes",
+        Rank::Four => "Fours",
+        Rank::Five => "Fives",
+        Rank::Six => "Sixes",
+        Rank::Seven => "Sevens",
+        Rank::Eight => "Eights",
+        Rank::Nine => "Nines",
+        Rank::Ten => "Tens",
+        Rank::Jack => "Jacks",
+        Rank::Queen => "Queens",
+        Rank::King => "Kings",
+        Rank::Ace => "Aces",
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvalError {
+    WrongCardCount(usize),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EvalError::WrongCardCount(count) => {
+                write!(f, "a poker hand needs 5 to 7 cards, got {}", count)
+            }
+        }
+    }
+}
+
+impl Error for EvalError {}
+
+/// Scores the best 5-card poker hand that can be made from 5 to 7 cards.
+///
+/// Works on rank bit masks and counts rather than trying every 5-card
+/// combination, so a 7-card Hold'em hand costs about as much as a 5-card one.
+/// Cards are assumed to be distinct.
+pub fn evaluate(cards: &[Card]) -> Result<HandRank, EvalError> {
+    if !(5..=7).contains(&cards.len()) {
+        return Err(EvalError::WrongCardCount(cards.len()));
+    }
+    Ok(evaluate_unchecked(cards))
+}
+
+/// Same as `evaluate` without the card count check, for hot loops where the
+/// caller already guarantees 5 to 7 cards
+pub fn evaluate_unchecked(cards: &[Card]) -> HandRank {
+    // One bit per rank (bit 0 = Two ... bit 12 = Ace), overall and per suit
+    let mut ranks_mask: u16 = 0;
+    let mut suit_masks = [0u16; 4];
+    let mut counts = [0u8; 13];
+
+    for card in cards {
+        let bit = 1 << card.rank as u16;
+        ranks_mask |= bit;
+        suit_masks[card.suit as usize] |= bit;
+        counts[card.rank as usize] += 1;
+    }
+
+    let flush_mask = suit_masks.iter().find(|mask| mask.count_ones() >= 5);
+
+    if let Some(&mask) = flush_mask
+        && let Some(high) = straight_high(mask)
+    {
+        return HandRank::new(HandCategory::StraightFlush, &[high]);
+    }
+
+    // Ranks grouped by how many times they appear, highest rank first
+    let mut quads = 0xFF;
+    let mut trips = [0xFFu8; 2];
+    let mut pairs = [0xFFu8; 3];
+    let (mut trip_count, mut pair_count) = (0, 0);
+    for rank in (0..13u8).rev() {
+        match counts[rank as usize] {
+            4.. if quads == 0xFF => quads = rank,
+            3 if trip_count < trips.len() => {
+                trips[trip_count] = rank;
+                trip_count += 1;
+            }
+            2 if pair_count < pairs.len() => {
+                pairs[pair_count] = rank;
+                pair_count += 1;
+            }
+            _ => {}
+        }
+    }
+
+    if quads != 0xFF {
+        let kicker = top_ranks(ranks_mask & !(1 << quads), 1);
+        return HandRank::new(HandCategory::FourOfAKind, &[quads, kicker[0]]);
+    }
+
+    if trip_count > 0 {
+        // With two sets of trips, the lower one can play as the pair
+        let second_trips = (trip_count > 1).then_some(trips[1]);
+        let best_pair = (pair_count > 0).then_some(pairs[0]);
+        if let Some(pair) = second_trips.max(best_pair) {
+            return HandRank::new(HandCategory::FullHouse, &[trips[0], pair]);
+        }
+    }
+
+    if let Some(&mask) = flush_mask {
+        return HandRank::new(HandCategory::Flush, &top_ranks(mask, 5));
+    }
+
+    if let Some(high) = straight_high(ranks_mask) {
+        return HandRank::new(HandCategory::Straight, &[high]);
+    }
+
+    if trip_count > 0 {
+        let kickers = top_ranks(ranks_mask & !(1 << trips[0]), 2);
+        return HandRank::new(
+            HandCategory::ThreeOfAKind,
+            &[trips[0], kickers[0], kickers[1]],
+        );
+    }
+
+    if pair_count >= 2 {
+        let kicker = top_ranks(ranks_mask & !(1 << pairs[0]) & !(1 << pairs[1]), 1);
+        return HandRank::new(HandCategory::TwoPair, &[pairs[0], pairs[1], kicker[0]]);
+    }
+
+    if pair_count == 1 {
+        let kickers = top_ranks(ranks_mask & !(1 << pairs[0]), 3);
+        return HandRank::new(
+            HandCategory::Pair,
+            &[pairs[0], kickers[0], kickers[1], kickers[2]],
+        );
+    }
+
+    HandRank::new(HandCategory::HighCard, &top_ranks(ranks_mask, 5))
+}
+
+/// Picks the 5 cards that make the best hand out of 5 to 7 cards.
+///
+/// Tries every combination, so prefer `evaluate` when only the score matters.
+pub fn best_five(cards: &[Card]) -> Result<([Card; 5], HandRank), EvalError> {
+    let n = cards.len();
+    if !(5..=7).contains(&n) {
+        return Err(EvalError::WrongCardCount(n));
+    }
+
+    let mut best: Option<([Card; 5], HandRank)> = None;
+    for a in 0..n {
+        for b in a + 1..n {
+            for c in b + 1..n {
+                for d in c + 1..n {
+                    for e in d + 1..n {
+                        let five = [cards[a], cards[b], cards[c], cards[d], cards[e]];
+                        let rank = evaluate_unchecked(&five);
+                        if best.is_none_or(|(_, best_rank)| rank > best_rank) {
+                            best = Some((five, rank));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // n >= 5 guarantees at least one combination
+    Ok(best.expect("at least one 5-card combination"))
+}
+
+/// Compares two hands of 5 to 7 cards each, kickers included
+pub fn compare(a: &[Card], b: &[Card]) -> Result<Ordering, EvalError> {
+    Ok(evaluate(a)?.cmp(&evaluate(b)?))
+}
+
+/// Highest rank of a 5-card run in `mask`, counting the Ace as low for the wheel
+fn straight_high(mask: u16) -> Option<u8> {
+    // Shift left and put the Ace at bit 0 too, so A-2-3-4-5 is five set bits
+    let extended = (mask << 1) | (mask >> 12 & 1);
+    (4..=13u8)
+        .rev()
+        .find(|&high| (extended >> (high - 4)) & 0b11111 == 0b11111)
+        .map(|high| high - 1)
+}
+
+/// The `n` highest ranks set in `mask`, highest first
+fn top_ranks(mask: u16, n: usize) -> [u8; 5] {
+    let mut ranks = [0u8; 5];
+    let mut found = 0;
+    for rank in (0..13u8).rev() {
+        if found == n {
+            break;
+        }
+        if mask & (1 << rank) != 0 {
+            ranks[found] = rank;
+            found += 1;
+        }
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::deck::Deck;
+    use crate::notation::parse_cards;
+    use crate::shuffle::{fisher_yates, seeded_rng};
+
+    fn rank_of(cards: &str) -> HandRank {
+        evaluate(&parse_cards(cards).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn every_five_card_hand_lands_in_the_known_category_counts() {
+        let cards = Deck::new().cards;
+        let mut counts = [0u32; 9];
+        for a in 0..52 {
+            for b in a + 1..52 {
+                for c in b + 1..52 {
+                    for d in c + 1..52 {
+                        for e in d + 1..52 {
+                            let five = [cards[a], cards[b], cards[c], cards[d], cards[e]];
+                            counts[evaluate_unchecked(&five).category() as usize] += 1;
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(
+            counts,
+            [
+                1_302_540, 1_098_240, 123_552, 54_912, 10_200, 5_108, 3_744, 624, 40
+            ]
+        );
+    }
+
+    #[test]
+    fn kickers_and_the_wheel_break_ties() {
+        assert!(rank_of("Ah Ad Kc 9s 3h") > rank_of("As Ac Qc Js Th"));
+        assert!(rank_of("Kh Kd 7c 7s 2h") < rank_of("Kc Ks 7d 7h 3c"));
+        assert_eq!(rank_of("Ah Ad Kc 9s 3h"), rank_of("As Ac Kd 9h 3c"));
+        // A-2-3-4-5 is the lowest straight
+        assert!(rank_of("Ah 2d 3c 4s 5h") < rank_of("2h 3d 4c 5s 6h"));
+        assert_eq!(rank_of("Ah 2d 3c 4s 5h").tie_breakers()[0], Rank::Five);
+        // Two sets of trips among seven cards make a full house
+        assert_eq!(
+            rank_of("Kh Kd Kc 5s 5h 5d 2c").to_string(),
+            "Full House, Kings over Fives"
+        );
+    }
+
+    // Depends on the machine and needs optimizations, so it only runs on
+    // request: `cargo test --release -- --ignored`
+    #[test]
+    #[ignore = "timing test, run with --release -- --ignored"]
+    fn evaluates_millions_of_seven_card_hands_per_second() {
+        let mut rng = seeded_rng(0);
+        let mut cards = Deck::new().cards;
+        let hands: Vec<[Card; 7]> = (0..100_000)
+            .map(|_| {
+                fisher_yates(&mut cards, &mut rng);
+                std::array::from_fn(|i| cards[i])
+            })
+            .collect();
+
+        let rounds = 20;
+        let start = Instant::now();
+        let mut checksum = 0u64;
+        for _ in 0..rounds {
+            for hand in &hands {
+                checksum += u64::from(evaluate_unchecked(hand).value());
+            }
+        }
+        let per_second = (hands.len() * rounds) as f64 / start.elapsed().as_secs_f64();
+        assert!(checksum > 0);
+        assert!(
+            per_second >= 1_000_000.0,
+            "only {:.0} hands per second",
+            per_second
+        );
+    }
+}
@@ -0,0 +1,433 @@
+use std::env;
+use std::fmt;
+use std::io::IsTerminal;
+
+use crate::card::{Card, Rank, Suit};
+
+/// Inner width of a card box, between the borders
+const CARD_INNER: usize = 5;
+
+/// Columns of a card left showing when another covers it, enough for "10♥"
+pub const FAN_COLUMNS: usize = 4;
+
+/// Which characters cards are drawn with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    /// Box-drawing characters and suit symbols
+    Unicode,
+    /// `+-|` borders and suit letters, for terminals that can't show more
+    Ascii,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    pub charset: Charset,
+    /// ANSI colors: red suits in red, card backs in blue
+    pub color: bool,
+}
+
+impl Style {
+    /// Picks what the terminal on stdout can show. Color needs a terminal
+    /// and is turned off by `NO_COLOR` (https://no-color.org) or
+    /// `TERM=dumb`; Unicode needs a UTF-8 locale.
+    pub fn detect() -> Self {
+        let term = env::var("TERM").unwrap_or_default();
+        let dumb = term == "dumb";
+        let color = std::io::stdout().is_terminal() && !dumb && env::var_os("NO_COLOR").is_none();
+
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default()
+            .to_uppercase();
+        let unicode = !dumb && (locale.contains("UTF-8") || locale.contains("UTF8"));
+
+        Style {
+            charset: if unicode {
+                Charset::Unicode
+            } else {
+                Charset::Ascii
+            },
+            color,
+        }
+    }
+
+    /// ASCII without color, safe anywhere (logs, pipes, tests)
+    pub fn plain() -> Self {
+        Style {
+            charset: Charset::Ascii,
+            color: false,
+        }
+    }
+
+    fn glyphs(&self) -> Glyphs {
+        match self.charset {
+            Charset::Unicode => Glyphs {
+                top_left: '┌',
+                top_right: '┐',
+                bottom_left: '└',
+                bottom_right: '┘',
+                horizontal: '─',
+                vertical: '│',
+                back: '░',
+                empty: '·',
+            },
+            Charset::Ascii => Glyphs {
+                top_left: '+',
+                top_right: '+',
+                bottom_left: '+',
+                bottom_right: '+',
+                horizontal: '-',
+                vertical: '|',
+                back: '#',
+                empty: '.',
+            },
+        }
+    }
+}
+
+struct Glyphs {
+    top_left: char,
+    top_right: char,
+    bottom_left: char,
+    bottom_right: char,
+    horizontal: char,
+    vertical: char,
+    back: char,
+    empty: char,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Blue,
+    Green,
+    Yellow,
+    Bold,
+}
+
+impl Color {
+    fn code(&self) -> &'static str {
+        match self {
+            Color::Red => "\x1b[31m",
+            Color::Blue => "\x1b[34m",
+            Color::Green => "\x1b[32m",
+            Color::Yellow => "\x1b[33m",
+            Color::Bold => "\x1b[1m",
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cell {
+    ch: char,
+    color: Option<Color>,
+}
+
+impl Cell {
+    const BLANK: Cell = Cell {
+        ch: ' ',
+        color: None,
+    };
+}
+
+/// A rectangle of characters to print: a card, a hand, a whole table.
+///
+/// Blocks are put together with `beside` and `above` and only turned into
+/// text by `render`, so colors never get in the way of lining things up.
+/// `Display` renders without color.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Block {
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Block {
+    /// Plain text, one row per line
+    pub fn text(text: &str) -> Self {
+        Block::painted(text, None)
+    }
+
+    pub fn colored(text: &str, color: Color) -> Self {
+        Block::painted(text, Some(color))
+    }
+
+    fn painted(text: &str, color: Option<Color>) -> Self {
+        let rows = text
+            .lines()
+            .map(|line| line.chars().map(|ch| Cell { ch, color }).collect())
+            .collect();
+        Block { rows }.padded()
+    }
+
+    /// A card face up:
+    ///
+    /// ```text
+    /// ┌─────┐
+    /// │10♥  │
+    /// │  ♥  │
+    /// │  10♥│
+    /// └─────┘
+    /// ```
+    pub fn card(card: &Card, style: &Style) -> Self {
+        let rank = match card.rank {
+            Rank::Ten => String::from("10"),
+            rank => rank.letter().to_string(),
+        };
+        let suit = match style.charset {
+            Charset::Unicode => card.suit.symbol(),
+            Charset::Ascii => card.suit.letter().to_ascii_uppercase(),
+        };
+        let color = suit_color(card.suit);
+
+        // Rank and suit in the corners so they still show when fanned
+        let corner = format!("{}{}", rank, suit);
+        let pad = " ".repeat(CARD_INNER - corner.chars().count());
+        let face = [
+            format!("{}{}", corner, pad),
+            format!("  {}  ", suit),
+            format!("{}{}", pad, corner),
+        ];
+        let mut block = Block::frame(style, |row| {
+            face[row].chars().map(|ch| Cell { ch, color }).collect()
+        });
+        // Keep the border uncolored, only the rank and suit are painted
+        for row in &mut block.rows[1..4] {
+            for cell in row.iter_mut().filter(|cell| cell.ch == ' ') {
+                cell.color = None;
+            }
+        }
+        block
+    }
+
+    /// A card face down
+    pub fn face_down(style: &Style) -> Self {
+        let back = style.glyphs().back;
+        Block::frame(style, |_| {
+            vec![
+                Cell {
+                    ch: back,
+                    color: Some(Color::Blue),
+                };
+                CARD_INNER
+            ]
+        })
+    }
+
+    /// Where a card could go, e.g. an empty foundation
+    pub fn empty_slot(style: &Style) -> Self {
+        let empty = style.glyphs().empty;
+        Block::frame(style, |row| {
+            let mut cells = vec![Cell::BLANK; CARD_INNER];
+            if row == 1 {
+                cells[CARD_INNER / 2].ch = empty;
+            }
+            cells
+        })
+    }
+
+    /// Cards spread left to right, each one partly covering the one before
+    pub fn hand(cards: &[Card], style: &Style) -> Self {
+        let blocks: Vec<Block> = cards.iter().map(|card| Block::card(card, style)).collect();
+        Block::fanned_right(&blocks, FAN_COLUMNS)
+    }
+
+    /// Blocks overlapping left to right with `visible` columns of each one
+    /// showing, the last one whole
+    pub fn fanned_right(blocks: &[Block], visible: usize) -> Self {
+        let Some((last, covered)) = blocks.split_last() else {
+            return Block::default();
+        };
+        let mut parts: Vec<Block> = covered
+            .iter()
+            .map(|block| block.cropped(block.height(), visible))
+            .collect();
+        parts.push(last.clone());
+        Block::beside(&parts, 0)
+    }
+
+    /// Blocks overlapping top to bottom with `visible` rows of each one
+    /// showing, the last one whole. Used for solitaire columns.
+    pub fn fanned_down(blocks: &[Block], visible: usize) -> Self {
+        let Some((last, covered)) = blocks.split_last() else {
+            return Block::default();
+        };
+        let mut parts: Vec<Block> = covered
+            .iter()
+            .map(|block| block.cropped(visible, block.width()))
+            .collect();
+        parts.push(last.clone());
+        Block::above(&parts)
+    }
+
+    /// Lays blocks out in a row, tops aligned, `gap` spaces apart
+    pub fn beside(blocks: &[Block], gap: usize) -> Self {
+        let height = blocks.iter().map(Block::height).max().unwrap_or(0);
+        let mut rows = vec![vec![]; height];
+        for (i, block) in blocks.iter().enumerate() {
+            let width = block.width();
+            for (y, row) in rows.iter_mut().enumerate() {
+                if i > 0 {
+                    row.extend(std::iter::repeat_n(Cell::BLANK, gap));
+                }
+                match block.rows.get(y) {
+                    Some(cells) => row.extend(cells.iter().copied()),
+                    None => row.extend(std::iter::repeat_n(Cell::BLANK, width)),
+                }
+            }
+        }
+        Block { rows }
+    }
+
+    /// Stacks blocks top to bottom, left aligned
+    pub fn above(blocks: &[Block]) -> Self {
+        let rows = blocks.iter().flat_map(|block| block.rows.clone()).collect();
+        Block { rows }.padded()
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows.first().map_or(0, Vec::len)
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Text with ANSI colors if `style` allows them
+    pub fn render(&self, style: &Style) -> String {
+        let mut out = String::new();
+        for row in &self.rows {
+            let mut current = None;
+            // Trailing blanks only make lines wrap on narrow terminals
+            let end = row
+                .iter()
+                .rposition(|cell| *cell != Cell::BLANK)
+                .map_or(0, |i| i + 1);
+            for cell in &row[..end] {
+                if style.color && cell.color != current {
+                    if current.is_some() {
+                        out.push_str(RESET);
+                    }
+                    if let Some(color) = cell.color {
+                        out.push_str(color.code());
+                    }
+                    current = cell.color;
+                }
+                out.push(cell.ch);
+            }
+            if style.color && current.is_some() {
+                out.push_str(RESET);
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// A card outline with `inside(row)` giving the 5 cells of each of the
+    /// three middle rows
+    fn frame<F: Fn(usize) -> Vec<Cell>>(style: &Style, inside: F) -> Self {
+        let glyphs = style.glyphs();
+        let plain = |ch| Cell { ch, color: None };
+        let edge = |left, right| {
+            let mut row = vec![plain(left)];
+            row.extend(std::iter::repeat_n(plain(glyphs.horizontal), CARD_INNER));
+            row.push(plain(right));
+            row
+        };
+
+        let mut rows = vec![edge(glyphs.top_left, glyphs.top_right)];
+        for row in 0..3 {
+            let mut cells = vec![plain(glyphs.vertical)];
+            cells.extend(inside(row));
+            cells.push(plain(glyphs.vertical));
+            rows.push(cells);
+        }
+        rows.push(edge(glyphs.bottom_left, glyphs.bottom_right));
+        Block { rows }
+    }
+
+    /// Pads every row to the width of the widest
+    fn padded(mut self) -> Self {
+        let width = self.rows.iter().map(Vec::len).max().unwrap_or(0);
+        for row in &mut self.rows {
+            row.resize(width, Cell::BLANK);
+        }
+        self
+    }
+
+    fn cropped(&self, height: usize, width: usize) -> Self {
+        let rows = self
+            .rows
+            .iter()
+            .take(height)
+            .map(|row| row.iter().take(width).copied().collect())
+            .collect();
+        Block { rows }
+    }
+}
+
+impl fmt::Display for Block {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.render(&Style::plain()))
+    }
+}
+
+fn suit_color(suit: Suit) -> Option<Color> {
+    if suit.is_red() {
+        Some(Color::Red)
+    } else {
+        Some(Color::Bold)
+    }
+}
+
+/// Clears the terminal before redrawing a table, when it understands ANSI
+pub fn clear_screen(style: &Style) -> &'static str {
+    if style.color { "\x1b[2J\x1b[H" } else { "" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::parse_cards;
+
+    #[test]
+    fn draws_a_card_without_color() {
+        let style = Style {
+            charset: Charset::Unicode,
+            color: false,
+        };
+        let ten = Card::new(Rank::Ten, Suit::Hearts);
+        assert_eq!(
+            Block::card(&ten, &style).render(&style),
+            "┌─────┐\n\
+             │10♥  │\n\
+             │  ♥  │\n\
+             │  10♥│\n\
+             └─────┘\n"
+        );
+    }
+
+    #[test]
+    fn fans_a_hand_beside_a_face_down_card() {
+        let style = Style::plain();
+        let hand = Block::hand(&parse_cards("As Kd").unwrap(), &style);
+        let row = Block::beside(&[hand, Block::face_down(&style)], 2);
+        assert_eq!(
+            row.render(&style),
+            "+---+-----+  +-----+\n\
+             |AS |KD   |  |#####|\n\
+             |  S|  D  |  |#####|\n\
+             |   |   KD|  |#####|\n\
+             +---+-----+  +-----+\n"
+        );
+
+        let colored = Style {
+            color: true,
+            ..style
+        };
+        assert!(row.render(&colored).contains('\x1b'));
+        assert!(!row.render(&style).contains('\x1b'));
+    }
+}
@@ -0,0 +1,521 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::card::{Card, Rank, Suit};
+use crate::hand::Hand;
+use crate::notation::CardFormat;
+
+/// Most deadwood a gin rummy player may knock with
+pub const KNOCK_LIMIT: u32 = 10;
+
+/// Most cards meld finding will look at. No rummy game deals a bigger hand,
+/// and the search grows quickly with every card and every wild.
+pub const MAX_CARDS: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeldError {
+    /// More than `MAX_CARDS` cards
+    TooManyCards(usize),
+}
+
+impl fmt::Display for MeldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MeldError::TooManyCards(count) => write!(
+                f,
+                "can't look for melds in {} cards, at most {}",
+                count, MAX_CARDS
+            ),
+        }
+    }
+}
+
+impl Error for MeldError {}
+
+/// Where Aces sit in a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AceRule {
+    /// A-2-3 only, as in gin rummy
+    Low,
+    /// Q-K-A only
+    High,
+    /// Either end, but a run can't turn the corner (K-A-2)
+    LowOrHigh,
+}
+
+/// What counts as a meld
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MeldRules {
+    pub aces: AceRule,
+    /// Ranks that stand in for any card. A meld still needs at least one
+    /// natural card, so wild cards on their own (three Twos with Twos wild)
+    /// are deadwood rather than a set.
+    pub wild_ranks: Vec<Rank>,
+    /// Fewest cards in a set or run
+    pub min_length: usize,
+}
+
+impl MeldRules {
+    /// Gin rummy: Aces low, no wild cards, melds of three or more
+    pub fn gin() -> Self {
+        MeldRules {
+            aces: AceRule::Low,
+            wild_ranks: vec![],
+            min_length: 3,
+        }
+    }
+
+    pub fn with_aces(mut self, aces: AceRule) -> Self {
+        self.aces = aces;
+        self
+    }
+
+    /// Makes every card of `rank` wild, e.g. Twos
+    pub fn with_wild(mut self, rank: Rank) -> Self {
+        self.wild_ranks.push(rank);
+        self
+    }
+
+    pub fn is_wild(&self, card: &Card) -> bool {
+        self.wild_ranks.contains(&card.rank)
+    }
+
+    /// Run positions a natural card can take, Ace low = 1 up to Ace high = 14
+    fn positions(&self, card: &Card) -> Vec<u8> {
+        match (card.rank, self.aces) {
+            (Rank::Ace, AceRule::Low) => vec![1],
+            (Rank::Ace, AceRule::High) => vec![14],
+            (Rank::Ace, AceRule::LowOrHigh) => vec![1, 14],
+            (rank, _) => vec![rank.value()],
+        }
+    }
+
+    fn lowest_position(&self) -> u8 {
+        match self.aces {
+            AceRule::High => 2,
+            AceRule::Low | AceRule::LowOrHigh => 1,
+        }
+    }
+
+    fn highest_position(&self) -> u8 {
+        match self.aces {
+            AceRule::Low => 13,
+            AceRule::High | AceRule::LowOrHigh => 14,
+        }
+    }
+}
+
+impl Default for MeldRules {
+    fn default() -> Self {
+        MeldRules::gin()
+    }
+}
+
+/// Deadwood value of a card: Aces 1, faces 10, others their pips
+pub fn card_points(card: &Card) -> u32 {
+    match card.rank {
+        Rank::Ace => 1,
+        Rank::Jack | Rank::Queen | Rank::King => 10,
+        rank => rank.value() as u32,
+    }
+}
+
+pub fn deadwood_points(cards: &[Card]) -> u32 {
+    cards.iter().map(card_points).sum()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MeldKind {
+    /// Cards of the same rank
+    Set,
+    /// Consecutive cards of one suit
+    Run,
+}
+
+/// A set or run. Run cards are in order, wild cards in the spot they fill.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Meld {
+    pub kind: MeldKind,
+    pub cards: Vec<Card>,
+}
+
+// "Run 5h 6h 7h"
+impl fmt::Display for Meld {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self.kind {
+            MeldKind::Set => "Set",
+            MeldKind::Run => "Run",
+        };
+        let hand: Hand = self.cards.iter().copied().collect();
+        write!(f, "{} {}", kind, hand.format(CardFormat::Short))
+    }
+}
+
+/// Every valid set and run that can be made from the cards in `hand`.
+///
+/// Melds overlap: a card can appear in several of them. Use
+/// `best_arrangement` to pick the ones to lay down. Every meld has at least
+/// one natural card, see `MeldRules::wild_ranks`.
+pub fn find_melds(hand: &Hand, rules: &MeldRules) -> Result<Vec<Meld>, MeldError> {
+    let melds = candidates(checked(hand)?, rules);
+    Ok(melds.into_iter().map(|(_, meld)| meld).collect())
+}
+
+/// Melds laid down and cards left over
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arrangement {
+    pub melds: Vec<Meld>,
+    pub deadwood: Vec<Card>,
+}
+
+impl Arrangement {
+    pub fn deadwood_points(&self) -> u32 {
+        deadwood_points(&self.deadwood)
+    }
+}
+
+/// Splits `hand` into non-overlapping melds so the deadwood left over is
+/// worth as few points as possible
+pub fn best_arrangement(hand: &Hand, rules: &MeldRules) -> Result<Arrangement, MeldError> {
+    let cards = checked(hand)?;
+    let melds = candidates(cards, rules);
+
+    let mut search = Search {
+        cards,
+        melds: &melds,
+        best: HashMap::new(),
+    };
+    search.least_deadwood(0);
+
+    // Walk back through the choices the search made
+    let mut arrangement = Arrangement {
+        melds: vec![],
+        deadwood: vec![],
+    };
+    let mut used = 0u64;
+    while let Some(&(_, choice)) = search.best.get(&used) {
+        match choice {
+            Choice::Deadwood(i) => {
+                arrangement.deadwood.push(cards[i]);
+                used |= 1 << i;
+            }
+            Choice::Meld(m) => {
+                arrangement.melds.push(melds[m].1.clone());
+                used |= melds[m].0;
+            }
+            Choice::Done => break,
+        }
+    }
+    Ok(arrangement)
+}
+
+/// Whether the hand may knock in gin rummy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KnockStatus {
+    /// No deadwood at all
+    Gin,
+    /// Deadwood of `KNOCK_LIMIT` points or less
+    Knock(u32),
+    CannotKnock(u32),
+}
+
+pub fn knock_status(hand: &Hand, rules: &MeldRules) -> Result<KnockStatus, MeldError> {
+    Ok(match best_arrangement(hand, rules)?.deadwood_points() {
+        0 => KnockStatus::Gin,
+        points if points <= KNOCK_LIMIT => KnockStatus::Knock(points),
+        points => KnockStatus::CannotKnock(points),
+    })
+}
+
+pub fn is_gin(hand: &Hand, rules: &MeldRules) -> Result<bool, MeldError> {
+    Ok(knock_status(hand, rules)? == KnockStatus::Gin)
+}
+
+pub fn can_knock(hand: &Hand, rules: &MeldRules) -> Result<bool, MeldError> {
+    Ok(!matches!(
+        knock_status(hand, rules)?,
+        KnockStatus::CannotKnock(_)
+    ))
+}
+
+/// The cards of `hand`, if there are few enough to search
+fn checked(hand: &Hand) -> Result<&[Card], MeldError> {
+    let cards = hand.cards();
+    if cards.len() > MAX_CARDS {
+        return Err(MeldError::TooManyCards(cards.len()));
+    }
+    Ok(cards)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Choice {
+    Deadwood(usize),
+    /// Index into the candidate melds
+    Meld(usize),
+    Done,
+}
+
+struct Search<'a> {
+    cards: &'a [Card],
+    melds: &'a [(u64, Meld)],
+    /// Least deadwood reachable once the cards in the key are placed, and
+    /// the choice that gets there
+    best: HashMap<u64, (u32, Choice)>,
+}
+
+impl Search<'_> {
+    /// Places the first card not yet in `used`, either as deadwood or in one
+    /// of its melds, so each arrangement is visited exactly once
+    fn least_deadwood(&mut self, used: u64) -> u32 {
+        if let Some(&(points, _)) = self.best.get(&used) {
+            return points;
+        }
+        let Some(first) = (0..self.cards.len()).find(|i| used & (1 << i) == 0) else {
+            self.best.insert(used, (0, Choice::Done));
+            return 0;
+        };
+
+        let mut best = (
+            card_points(&self.cards[first]) + self.least_deadwood(used | 1 << first),
+            Choice::Deadwood(first),
+        );
+        for m in 0..self.melds.len() {
+            let mask = self.melds[m].0;
+            if mask & (1 << first) == 0 || mask & used != 0 {
+                continue;
+            }
+            let points = self.least_deadwood(used | mask);
+            if points < best.0 {
+                best = (points, Choice::Meld(m));
+            }
+        }
+        self.best.insert(used, best);
+        best.0
+    }
+}
+
+/// Every meld with the mask of hand positions it uses, without duplicates
+fn candidates(cards: &[Card], rules: &MeldRules) -> Vec<(u64, Meld)> {
+    let wilds: Vec<usize> = (0..cards.len())
+        .filter(|i| rules.is_wild(&cards[*i]))
+        .collect();
+    let naturals: Vec<usize> = (0..cards.len())
+        .filter(|i| !rules.is_wild(&cards[*i]))
+        .collect();
+
+    let mut melds: Vec<(u64, Meld)> = vec![];
+    let mut push = |mask: u64, kind: MeldKind, positions: &[usize]| {
+        if !melds.iter().any(|(other, _)| *other == mask) {
+            let cards = positions.iter().map(|i| cards[*i]).collect();
+            melds.push((mask, Meld { kind, cards }));
+        }
+    };
+
+    // Sets: naturals of one rank in different suits, topped up with wilds
+    for rank in Rank::ALL {
+        let same: Vec<usize> = naturals
+            .iter()
+            .copied()
+            .filter(|i| cards[*i].rank == rank)
+            .collect();
+        for size in 1..=Suit::ALL.len() {
+            combinations(&same, size, &mut vec![], &mut |subset| {
+                let mut suits: Vec<Suit> = subset.iter().map(|i| cards[*i].suit).collect();
+                suits.sort();
+                suits.dedup();
+                if suits.len() != subset.len() {
+                    return;
+                }
+                let least = rules.min_length.saturating_sub(size);
+                for extra in least..=Suit::ALL.len() - size {
+                    combinations(&wilds, extra, &mut subset.to_vec(), &mut |members| {
+                        push(mask_of(members), MeldKind::Set, members)
+                    });
+                }
+            });
+        }
+    }
+
+    // Runs: every window of positions in each suit, each spot filled by a
+    // natural card or a wild
+    for suit in Suit::ALL {
+        let mut at_position: HashMap<u8, Vec<usize>> = HashMap::new();
+        for i in naturals.iter().copied().filter(|i| cards[*i].suit == suit) {
+            for position in rules.positions(&cards[i]) {
+                at_position.entry(position).or_default().push(i);
+            }
+        }
+        let low = rules.lowest_position();
+        let high = rules.highest_position();
+        for start in low..=high {
+            for end in start..=high {
+                let length = (end - start + 1) as usize;
+                if length < rules.min_length {
+                    continue;
+                }
+                let window: Vec<&[usize]> = (start..=end)
+                    .map(|p| at_position.get(&p).map_or(&[][..], Vec::as_slice))
+                    .collect();
+                fill_run(&window, &wilds, &mut vec![], &mut |members| {
+                    push(mask_of(members), MeldKind::Run, members)
+                });
+            }
+        }
+    }
+    melds
+}
+
+/// Picks one card for each spot of a run window. `None` spots take the next
+/// unused wild; `chosen` must end up with at least one natural card.
+fn fill_run(
+    window: &[&[usize]],
+    wilds: &[usize],
+    chosen: &mut Vec<Option<usize>>,
+    found: &mut dyn FnMut(&[usize]),
+) {
+    if chosen.len() == window.len() {
+        if chosen.iter().all(Option::is_none) {
+            return;
+        }
+        let gaps = chosen.iter().filter(|spot| spot.is_none()).count();
+        combinations(wilds, gaps, &mut vec![], &mut |filling| {
+            let mut filling = filling.iter();
+            let members: Vec<usize> = chosen
+                .iter()
+                .map(|spot| spot.unwrap_or_else(|| *filling.next().expect("one wild per gap")))
+                .collect();
+            found(&members);
+        });
+        return;
+    }
+
+    let spot = window[chosen.len()];
+    for i in spot {
+        // The same card can't fill two spots (an Ace at both ends)
+        if chosen.contains(&Some(*i)) {
+            continue;
+        }
+        chosen.push(Some(*i));
+        fill_run(window, wilds, chosen, found);
+        chosen.pop();
+    }
+    let gaps = chosen.iter().filter(|spot| spot.is_none()).count();
+    if gaps < wilds.len() {
+        chosen.push(None);
+        fill_run(window, wilds, chosen, found);
+        chosen.pop();
+    }
+}
+
+/// Calls `visit` with `chosen` followed by each way of picking `size` of
+/// `items`, in order, one at a time
+fn combinations(
+    items: &[usize],
+    size: usize,
+    chosen: &mut Vec<usize>,
+    visit: &mut dyn FnMut(&[usize]),
+) {
+    if size == 0 {
+        visit(chosen);
+        return;
+    }
+    for (i, item) in items.iter().enumerate() {
+        if items.len() - i < size {
+            break;
+        }
+        chosen.push(*item);
+        combinations(&items[i + 1..], size - 1, chosen, visit);
+        chosen.pop();
+    }
+}
+
+fn mask_of(members: &[usize]) -> u64 {
+    members.iter().fold(0, |mask, i| mask | 1 << i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deck::Deck;
+
+    fn hand(cards: &str) -> Hand {
+        cards.parse().unwrap()
+    }
+
+    #[test]
+    fn finds_gin_and_the_least_deadwood() {
+        let rules = MeldRules::gin();
+        assert_eq!(
+            is_gin(&hand("Ah 2h 3h 7c 7d 7s Js Qs Ks"), &rules),
+            Ok(true)
+        );
+
+        // 5h 5d 5c and 5c 6c 7c overlap: the run keeps more points out
+        let arrangement = best_arrangement(&hand("5h 5d 5c 6c 7c Kd"), &rules).unwrap();
+        assert_eq!(arrangement.deadwood_points(), 20);
+        assert_eq!(
+            knock_status(&hand("5h 5d 5c 6c 7c 2d"), &rules),
+            Ok(KnockStatus::CannotKnock(12))
+        );
+        assert_eq!(
+            knock_status(&hand("7c 8c 9c 4d 3h 2s"), &rules),
+            Ok(KnockStatus::Knock(9))
+        );
+    }
+
+    #[test]
+    fn wild_cards_alone_are_not_a_meld() {
+        let rules = MeldRules::gin().with_wild(Rank::Two);
+        assert_eq!(find_melds(&hand("2h 2d 2c"), &rules), Ok(vec![]));
+        assert_eq!(
+            knock_status(&hand("2h 2d 2c"), &rules),
+            Ok(KnockStatus::Knock(6))
+        );
+        // With one natural card they make a set or a run
+        assert_eq!(is_gin(&hand("2h 2d 9c"), &rules), Ok(true));
+    }
+
+    #[test]
+    fn refuses_more_cards_than_it_will_search() {
+        let hand = Hand::from(Deck::new().cards[..MAX_CARDS + 1].to_vec());
+        let rules = MeldRules::gin();
+        let too_many = MeldError::TooManyCards(MAX_CARDS + 1);
+        assert_eq!(best_arrangement(&hand, &rules).err(), Some(too_many));
+        assert_eq!(is_gin(&hand, &rules), Err(too_many));
+        assert_eq!(find_melds(&hand, &rules), Err(too_many));
+    }
+
+    #[test]
+    fn a_hand_of_wilds_stays_quick() {
+        // Eight wilds: sets and runs take fixed-size picks of them rather
+        // than going through all 256 subsets for every candidate
+        let rules = MeldRules::gin().with_wild(Rank::Two).with_wild(Rank::Three);
+        let cards = "2c 2d 2h 2s 3c 3d 3h 3s 9c 9d Jh Qh 5d 6d 7s 8s";
+        let hand = hand(cards);
+        assert_eq!(hand.cards().len(), MAX_CARDS);
+        assert_eq!(is_gin(&hand, &rules), Ok(true));
+    }
+
+    #[test]
+    fn combinations_pick_each_subset_of_a_size_once() {
+        let mut seen = vec![];
+        combinations(&[1, 2, 3, 4], 2, &mut vec![0], &mut |picked| {
+            seen.push(picked.to_vec())
+        });
+        assert_eq!(
+            seen,
+            [
+                [0, 1, 2],
+                [0, 1, 3],
+                [0, 1, 4],
+                [0, 2, 3],
+                [0, 2, 4],
+                [0, 3, 4]
+            ]
+        );
+        let mut count = 0;
+        combinations(&[1, 2], 3, &mut vec![], &mut |_| count += 1);
+        assert_eq!(count, 0);
+    }
+}
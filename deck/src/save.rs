@@ -0,0 +1,380 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::card::Card;
+use crate::deck::Deck;
+use crate::hand::Hand;
+use crate::notation::{CardFormat, ParseCardError, parse_cards};
+
+/// Version written at the top of every save file. Bump it when the format
+/// changes and keep reading the old versions.
+pub const SAVE_VERSION: u32 = 1;
+
+const HEADER: &str = "card-game-save";
+
+/// Why a save file couldn't be read back
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    /// The first line isn't a save header
+    NotASave,
+    /// Written by a newer version of the program
+    UnsupportedVersion(u32),
+    /// The file has no `deck` line
+    MissingDeck,
+    /// A line that doesn't fit the format, numbered from 1
+    Malformed {
+        line: usize,
+        reason: String,
+    },
+    BadCard {
+        line: usize,
+        source: ParseCardError,
+    },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io(err) => write!(f, "can't read save: {}", err),
+            LoadError::NotASave => f.write_str("not a saved game"),
+            LoadError::UnsupportedVersion(version) => write!(
+                f,
+                "save version {} is newer than supported version {}",
+                version, SAVE_VERSION
+            ),
+            LoadError::MissingDeck => f.write_str("save has no deck"),
+            LoadError::Malformed { line, reason } => write!(f, "line {}: {}", line, reason),
+            LoadError::BadCard { line, source } => write!(f, "line {}: {}", line, source),
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::Io(err) => Some(err),
+            LoadError::BadCard { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// A pile name that wouldn't read back the same from a save file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PileNameError(pub String);
+
+impl fmt::Display for PileNameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "pile name {:?} can't contain ':' or line breaks, or start or end with spaces",
+            self.0
+        )
+    }
+}
+
+impl Error for PileNameError {}
+
+impl From<io::Error> for LoadError {
+    fn from(err: io::Error) -> Self {
+        LoadError::Io(err)
+    }
+}
+
+/// Exact position of a ChaCha RNG: its key, stream and how many words of
+/// output it has produced. Restoring it gives back the very same future
+/// numbers, which a seed alone can't once the game has used some.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RngState {
+    pub seed: [u8; 32],
+    pub stream: u64,
+    pub word_pos: u128,
+}
+
+impl RngState {
+    pub fn capture(rng: &ChaCha8Rng) -> Self {
+        RngState {
+            seed: rng.get_seed(),
+            stream: rng.get_stream(),
+            word_pos: rng.get_word_pos(),
+        }
+    }
+
+    pub fn restore(&self) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::from_seed(self.seed);
+        rng.set_stream(self.stream);
+        rng.set_word_pos(self.word_pos);
+        rng
+    }
+}
+
+/// A snapshot of a game in progress: the deck in its exact order with its
+/// discard pile, every hand, any other named piles, and the RNG the game
+/// shuffles with.
+///
+/// Games that shuffle again after the deal expose their RNG for `with_rng`:
+/// `Shoe::rng` (and so the blackjack `Table`), `CrazyEights::rng` and
+/// `War::rng`.
+///
+/// Saved as plain text, one pile per line in short card notation, under a
+/// versioned header:
+///
+/// ```text
+/// card-game-save 1
+/// deck-seed 42
+/// rng <seed hex> <stream> <word position>
+/// deck 4h Qs 2c ...
+/// discards 7d
+/// hand Js 9c
+/// hand Ad Kd
+/// pile kitty: 5c 8h
+/// ```
+///
+/// Piles list their cards bottom first, so the last card of `deck` is the
+/// next one drawn.
+#[derive(Debug, Clone)]
+pub struct SavedGame {
+    pub deck: Deck,
+    /// In seat order
+    pub hands: Vec<Hand>,
+    /// Anything else on the table, e.g. a kitty, community cards or a
+    /// solitaire waste pile
+    pub piles: BTreeMap<String, Hand>,
+    pub rng: Option<RngState>,
+}
+
+impl SavedGame {
+    pub fn new(deck: Deck) -> Self {
+        SavedGame {
+            deck,
+            hands: vec![],
+            piles: BTreeMap::new(),
+            rng: None,
+        }
+    }
+
+    pub fn with_hands(mut self, hands: Vec<Hand>) -> Self {
+        self.hands = hands;
+        self
+    }
+
+    /// Adds a named pile. Names can't contain ':' or line breaks, nor start
+    /// or end with whitespace, as none of that survives being read back.
+    pub fn with_pile(mut self, name: &str, pile: Hand) -> Result<Self, PileNameError> {
+        if name.contains([':', '\n', '\r']) || name.trim() != name {
+            return Err(PileNameError(name.to_string()));
+        }
+        self.piles.insert(name.to_string(), pile);
+        Ok(self)
+    }
+
+    /// Records where `rng` is, so the restored game shuffles the same way
+    pub fn with_rng(mut self, rng: &ChaCha8Rng) -> Self {
+        self.rng = Some(RngState::capture(rng));
+        self
+    }
+
+    /// The game's RNG, at the position it was saved
+    pub fn restore_rng(&self) -> Option<ChaCha8Rng> {
+        self.rng.as_ref().map(RngState::restore)
+    }
+
+    /// Writes the save to `path`. The file is written next to it first and
+    /// then renamed, so a crash mid-save can't leave a half-written game.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut partial = path.as_os_str().to_owned();
+        partial.push(".partial");
+        fs::write(&partial, self.to_string())?;
+        fs::rename(&partial, path)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, LoadError> {
+        fs::read_to_string(path)?.parse()
+    }
+}
+
+impl fmt::Display for SavedGame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} {}", HEADER, SAVE_VERSION)?;
+        if let Some(seed) = self.deck.seed() {
+            writeln!(f, "deck-seed {}", seed)?;
+        }
+        if let Some(rng) = &self.rng {
+            let seed: String = rng
+                .seed
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect();
+            writeln!(f, "rng {} {} {}", seed, rng.stream, rng.word_pos)?;
+        }
+        writeln!(f, "{}", line("deck", &self.deck.cards))?;
+        writeln!(f, "{}", line("discards", self.deck.discards()))?;
+        for hand in &self.hands {
+            writeln!(f, "{}", line("hand", hand.cards()))?;
+        }
+        for (name, pile) in &self.piles {
+            writeln!(f, "{}", line(&format!("pile {}:", name), pile.cards()))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SavedGame {
+    type Err = LoadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate().map(|(i, text)| (i + 1, text.trim()));
+
+        let version = match lines.next() {
+            Some((_, first)) => match first.split_once(' ') {
+                Some((HEADER, version)) => version.parse().map_err(|_| LoadError::NotASave)?,
+                _ => return Err(LoadError::NotASave),
+            },
+            None => return Err(LoadError::NotASave),
+        };
+        if version > SAVE_VERSION {
+            return Err(LoadError::UnsupportedVersion(version));
+        }
+
+        let mut deck_seed = None;
+        let mut rng = None;
+        let mut cards = None;
+        let mut discards = vec![];
+        let mut hands = vec![];
+        let mut piles = BTreeMap::new();
+        for (number, text) in lines {
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            let malformed = |reason: &str| LoadError::Malformed {
+                line: number,
+                reason: reason.to_string(),
+            };
+            let read_cards = |list: &str| {
+                parse_cards(list).map_err(|source| LoadError::BadCard {
+                    line: number,
+                    source,
+                })
+            };
+
+            let (key, rest) = text.split_once(' ').unwrap_or((text, ""));
+            match key {
+                "deck-seed" => {
+                    deck_seed = Some(rest.parse().map_err(|_| malformed("bad deck seed"))?)
+                }
+                "rng" => rng = Some(parse_rng(rest).ok_or_else(|| malformed("bad rng state"))?),
+                "deck" => cards = Some(read_cards(rest)?),
+                "discards" => discards = read_cards(rest)?,
+                "hand" => hands.push(Hand::from(read_cards(rest)?)),
+                "pile" => {
+                    let (name, list) = rest
+                        .split_once(':')
+                        .ok_or_else(|| malformed("pile without a name"))?;
+                    piles.insert(name.trim().to_string(), Hand::from(read_cards(list)?));
+                }
+                other => return Err(malformed(&format!("unknown entry '{}'", other))),
+            }
+        }
+
+        let cards = cards.ok_or(LoadError::MissingDeck)?;
+        Ok(SavedGame {
+            deck: Deck::restore(cards, deck_seed, discards),
+            hands,
+            piles,
+            rng,
+        })
+    }
+}
+
+/// "hand Js 9c", or just "hand" when empty
+fn line(key: &str, cards: &[Card]) -> String {
+    let mut line = key.to_string();
+    for card in cards {
+        line.push(' ');
+        line.push_str(&card.format(CardFormat::Short));
+    }
+    line
+}
+
+/// "<64 hex digits> <stream> <word position>"
+fn parse_rng(text: &str) -> Option<RngState> {
+    let mut parts = text.split_whitespace();
+    let hex = parts.next()?;
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+    let mut seed = [0u8; 32];
+    for (i, byte) in seed.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).ok()?;
+    }
+    let state = RngState {
+        seed,
+        stream: parts.next()?.parse().ok()?,
+        word_pos: parts.next()?.parse().ok()?,
+    };
+    parts.next().is_none().then_some(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::RngCore;
+
+    use super::*;
+    use crate::eights::CrazyEights;
+    use crate::notation::parse_cards;
+    use crate::shoe::Shoe;
+    use crate::source::CardSource;
+
+    #[test]
+    fn round_trips_through_text_with_the_rng_position() {
+        let mut deck = Deck::new();
+        deck.shufle_seeded(9);
+        let hand = deck.deal(5).unwrap();
+        let mut rng = crate::shuffle::seeded_rng(9);
+        rng.next_u64();
+
+        let saved = SavedGame::new(deck.clone())
+            .with_hands(vec![hand.clone(), Hand::new()])
+            .with_pile("kitty", Hand::from(parse_cards("5c 8h").unwrap()))
+            .unwrap()
+            .with_rng(&rng);
+        let loaded: SavedGame = saved.to_string().parse().unwrap();
+
+        assert_eq!(loaded.deck.cards, deck.cards);
+        assert_eq!(loaded.deck.seed(), Some(9));
+        assert_eq!(loaded.hands, vec![hand, Hand::new()]);
+        assert_eq!(loaded.piles, saved.piles);
+        assert_eq!(loaded.restore_rng().unwrap().next_u64(), rng.next_u64());
+    }
+
+    #[test]
+    fn captures_the_rng_of_games_that_reshuffle() {
+        let mut shoe = Shoe::with_seed(2, 5).with_penetration(0.0);
+        shoe.draw_one();
+        shoe.reshuffle_if_needed();
+        let saved = SavedGame::new(Deck::new()).with_rng(shoe.rng());
+        assert_eq!(saved.restore_rng().as_ref(), Some(shoe.rng()));
+
+        let game = CrazyEights::deal(3, 5).unwrap();
+        let saved = SavedGame::new(Deck::new()).with_rng(game.rng());
+        assert_eq!(saved.restore_rng().as_ref(), Some(game.rng()));
+    }
+
+    #[test]
+    fn rejects_pile_names_that_would_not_read_back() {
+        for name in ["a:b", "two\nlines", "cr\r", " padded", "padded "] {
+            let result = SavedGame::new(Deck::new()).with_pile(name, Hand::new());
+            assert_eq!(result.err(), Some(PileNameError(name.to_string())));
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use rand_chacha::ChaCha8Rng;
+
+use crate::card::{Card, Rank};
+use crate::counting::{CardCounting, DECK_SIZE, hi_lo};
+use crate::deck::Deck;
+use crate::shuffle::{fisher_yates, random_seed, seeded_rng};
+use crate::source::CardSource;
+
+/// Several decks shuffled together, casino style.
+///
+/// A cut card is placed `penetration` of the way into the shoe. Once it comes
+/// out the current round is finished as usual and `reshuffle_if_needed`
+/// shuffles everything back in before the next one. The shoe owns its RNG, so
+/// it can also reshuffle itself if it runs dry mid-round: only the discards
+/// go back in then, as the cards of the current round are still in play.
+#[derive(Debug)]
+pub struct Shoe {
+    decks: usize,
+    // Every card the shoe holds when full, in the order the decks were added
+    composition: Vec<Card>,
+    // Top of the shoe is the end of the vector, like `Deck`
+    cards: Vec<Card>,
+    dealt: Vec<Card>,
+    // Cards in `dealt` before this index were dealt in earlier rounds and are
+    // in the discard tray
+    round_start: usize,
+    burned: Vec<Card>,
+    cut_card: usize,
+    burn_on_shuffle: usize,
+    shuffles: usize,
+    seed: u64,
+    rng: ChaCha8Rng,
+}
+
+/// Where the shoe stands since its last shuffle
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShoeStats {
+    pub decks: usize,
+    pub total: usize,
+    pub dealt: usize,
+    pub burned: usize,
+    pub remaining: usize,
+    /// Share of the shoe already out (dealt or burned), from 0.0 to 1.0
+    pub penetration: f64,
+    pub shuffles: usize,
+    /// Cards of each rank dealt and still in the shoe, indexed by `Rank as usize`
+    pub dealt_by_rank: [usize; 13],
+    pub remaining_by_rank: [usize; 13],
+}
+
+impl Shoe {
+    pub const DEFAULT_PENETRATION: f64 = 0.75;
+
+    /// A shuffled shoe of `decks` standard decks
+    pub fn new(decks: usize) -> Self {
+        Shoe::with_seed(decks, random_seed())
+    }
+
+    /// A shoe of `decks` standard decks, shuffled reproducibly from `seed`
+    pub fn with_seed(decks: usize, seed: u64) -> Self {
+        Shoe::from_decks((0..decks).map(|_| Deck::new()).collect(), seed)
+    }
+
+    /// Puts any decks (standard or not) into one shoe and shuffles them
+    pub fn from_decks(decks: Vec<Deck>, seed: u64) -> Self {
+        let count = decks.len();
+        let composition: Vec<Card> = decks.into_iter().flat_map(|deck| deck.cards).collect();
+        let mut shoe = Shoe {
+            decks: count,
+            cut_card: cut_position(composition.len(), Shoe::DEFAULT_PENETRATION),
+            cards: vec![],
+            dealt: vec![],
+            round_start: 0,
+            burned: vec![],
+            composition,
+            burn_on_shuffle: 1,
+            shuffles: 0,
+            seed,
+            rng: seeded_rng(seed),
+        };
+        shoe.shufle();
+        shoe
+    }
+
+    /// Places the cut card `penetration` (0.0 to 1.0) of the way into the shoe
+    pub fn with_penetration(mut self, penetration: f64) -> Self {
+        self.cut_card = cut_position(self.composition.len(), penetration);
+        self
+    }
+
+    /// Number of cards burned face down after every shuffle
+    pub fn with_burn(mut self, burn: usize) -> Self {
+        // Put back what the first shuffle burned, then burn the new amount
+        while let Some(card) = self.burned.pop() {
+            self.cards.push(card);
+        }
+        self.burn_on_shuffle = burn;
+        self.burn(burn);
+        self
+    }
+
+    /// Places the cut card after `position` cards have left the shoe
+    pub fn set_cut_card(&mut self, position: usize) {
+        self.cut_card = position.min(self.composition.len());
+    }
+
+    pub fn cut_card(&self) -> usize {
+        self.cut_card
+    }
+
+    pub fn decks(&self) -> usize {
+        self.decks
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Where the shoe's RNG is now. The seed only replays the first shuffle,
+    /// this also gives the later ones, see `SavedGame::with_rng`.
+    pub fn rng(&self) -> &ChaCha8Rng {
+        &self.rng
+    }
+
+    /// True once the cut card has come out
+    pub fn needs_shuffle(&self) -> bool {
+        self.dealt.len() + self.burned.len() >= self.cut_card
+    }
+
+    /// Collects every card, shuffles and burns. Call between rounds, or use
+    /// `reshuffle_if_needed` to only do it once the cut card is out.
+    pub fn shufle(&mut self) {
+        self.cards.clone_from(&self.composition);
+        self.dealt.clear();
+        self.round_start = 0;
+        self.burned.clear();
+        fisher_yates(&mut self.cards, &mut self.rng);
+        self.shuffles += 1;
+        self.burn(self.burn_on_shuffle);
+    }
+
+    /// Reshuffles if the cut card has come out, returns whether it did.
+    ///
+    /// Marks the start of a new round either way: everything dealt so far is
+    /// in the discards, which a mid-round reshuffle can put back in.
+    pub fn reshuffle_if_needed(&mut self) -> bool {
+        self.round_start = self.dealt.len();
+        if self.needs_shuffle() {
+            self.shufle();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Shuffles the discards and burned cards into what is left of the shoe,
+    /// leaving out the cards dealt this round
+    fn shufle_discards(&mut self) {
+        if self.round_start == 0 && self.burned.is_empty() {
+            return;
+        }
+        self.cards.extend(self.dealt.drain(..self.round_start));
+        self.cards.append(&mut self.burned);
+        self.round_start = 0;
+        fisher_yates(&mut self.cards, &mut self.rng);
+        self.shuffles += 1;
+        self.burn(self.burn_on_shuffle);
+    }
+
+    /// Discards up to `count` cards from the top without dealing them
+    pub fn burn(&mut self, count: usize) {
+        for _ in 0..count {
+            match self.cards.pop() {
+                Some(card) => self.burned.push(card),
+                None => break,
+            }
+        }
+    }
+
+    /// Cards dealt since the last shuffle, in the order they came out
+    pub fn dealt(&self) -> &[Card] {
+        &self.dealt
+    }
+
+    pub fn stats(&self) -> ShoeStats {
+        let mut dealt_by_rank = [0; 13];
+        for card in &self.dealt {
+            dealt_by_rank[card.rank as usize] += 1;
+        }
+        let mut remaining_by_rank = [0; 13];
+        for card in &self.cards {
+            remaining_by_rank[card.rank as usize] += 1;
+        }
+
+        let total = self.composition.len();
+        let out = self.dealt.len() + self.burned.len();
+        ShoeStats {
+            decks: self.decks(),
+            total,
+            dealt: self.dealt.len(),
+            burned: self.burned.len(),
+            remaining: self.cards.len(),
+            penetration: if total == 0 {
+                0.0
+            } else {
+                out as f64 / total as f64
+            },
+            shuffles: self.shuffles,
+            dealt_by_rank,
+            remaining_by_rank,
+        }
+    }
+
+    /// How many cards of `rank` are still in the shoe
+    pub fn remaining_of(&self, rank: Rank) -> usize {
+        self.cards.iter().filter(|card| card.rank == rank).count()
+    }
+
+    /// A shoe that deals exactly `cards`, first card first, and never
+    /// reshuffles before they are all out
+    #[cfg(test)]
+    pub(crate) fn stacked(cards: Vec<Card>) -> Self {
+        Shoe {
+            decks: 1,
+            cut_card: cards.len(),
+            composition: cards.clone(),
+            cards: cards.into_iter().rev().collect(),
+            dealt: vec![],
+            round_start: 0,
+            burned: vec![],
+            burn_on_shuffle: 0,
+            shuffles: 0,
+            seed: 0,
+            rng: seeded_rng(0),
+        }
+    }
+}
+
+impl CardSource for Shoe {
+    /// Takes the top card. If the shoe is empty mid-round the discards are
+    /// reshuffled on the spot, so it only runs dry once every card is out in
+    /// the current round.
+    fn draw_one(&mut self) -> Option<Card> {
+        if self.cards.is_empty() {
+            self.shufle_discards();
+        }
+        let card = self.cards.pop()?;
+        self.dealt.push(card);
+        Some(card)
+    }
+
+    /// Cards left before a mid-round reshuffle
+    fn remaining(&self) -> usize {
+        self.cards.len()
+    }
+}
+
+impl CardCounting for Shoe {
+    fn remaining_cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// Only cards dealt face up count, burned cards were never seen
+    fn running_count(&self) -> i32 {
+        self.dealt.iter().map(hi_lo).sum()
+    }
+
+    /// Burned cards are as unknown as the ones still in the shoe
+    fn decks_remaining(&self) -> f64 {
+        (self.cards.len() + self.burned.len()) as f64 / DECK_SIZE
+    }
+}
+
+fn cut_position(total: usize, penetration: f64) -> usize {
+    (total as f64 * penetration.clamp(0.0, 1.0)).round() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn running_dry_mid_round_leaves_the_cards_in_play_out() {
+        let mut shoe = Shoe::with_seed(1, 3).with_penetration(1.0).with_burn(0);
+        let first_round = shoe.deal(30).unwrap();
+        assert!(!shoe.reshuffle_if_needed());
+
+        // 22 cards left, then the 30 discards are shuffled back in
+        let second_round = shoe.deal_up_to(40);
+        assert_eq!(second_round.len(), 40);
+        assert_eq!(shoe.stats().shuffles, 2);
+        let mut in_play: Vec<Card> = second_round.iter().copied().collect();
+        in_play.sort();
+        in_play.dedup();
+        assert_eq!(in_play.len(), 40);
+        assert_eq!(shoe.remaining(), 12);
+        assert!(first_round.iter().any(|card| !in_play.contains(card)));
+    }
+
+    #[test]
+    fn runs_dry_once_every_card_is_out_this_round() {
+        let mut shoe = Shoe::with_seed(1, 3).with_burn(0);
+        assert_eq!(shoe.deal_up_to(60).len(), 52);
+        assert_eq!(shoe.draw_one(), None);
+    }
+}
@@ -0,0 +1,45 @@
+pub mod models;
+pub mod quality;
+
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+pub use models::{ShuffleSequence, ShuffleStep};
+
+/// Builds the RNG used for every seeded shuffle.
+///
+/// ChaCha8 is specified independently of the platform (no `usize`, no
+/// endianness surprises), so a seed replays the same stream everywhere.
+pub fn seeded_rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+/// Picks a fresh seed from the thread RNG
+pub fn random_seed() -> u64 {
+    rand::rng().next_u64()
+}
+
+/// Uniform index in `0..bound`, using rejection sampling on 32-bit draws.
+///
+/// Written out by hand rather than using `Rng::random_range` so the mapping
+/// from RNG output to card positions can't change under a `rand` upgrade.
+pub fn bounded_index<R: RngCore + ?Sized>(rng: &mut R, bound: usize) -> usize {
+    debug_assert!(bound > 0 && bound <= u32::MAX as usize);
+    let bound = bound as u32;
+    // Largest multiple of `bound` that fits in a u32, anything above is rejected
+    let zone = u32::MAX - (u32::MAX - bound + 1) % bound;
+    loop {
+        let value = rng.next_u32();
+        if value <= zone {
+            return (value % bound) as usize;
+        }
+    }
+}
+
+/// In-place Fisher–Yates shuffle, walking from the end of the slice down
+pub fn fisher_yates<T, R: RngCore + ?Sized>(items: &mut [T], rng: &mut R) {
+    for i in (1..items.len()).rev() {
+        let j = bounded_index(rng, i + 1);
+        items.swap(i, j);
+    }
+}
@@ -0,0 +1,231 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use rand::RngCore;
+
+use super::{bounded_index, fisher_yates};
+
+/// Uniform float in `[0, 1)` built from the top 53 bits of one draw
+fn unit<R: RngCore + ?Sized>(rng: &mut R) -> f64 {
+    (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Number of heads in `n` fair coin flips
+fn binomial_half<R: RngCore + ?Sized>(rng: &mut R, n: usize) -> usize {
+    let mut heads = 0;
+    let mut left = n;
+    while left > 0 {
+        let take = left.min(64);
+        let bits = rng.next_u64() & (u64::MAX >> (64 - take));
+        heads += bits.count_ones() as usize;
+        left -= take;
+    }
+    heads
+}
+
+/// Riffle shuffle following the Gilbert–Shannon–Reeds model.
+///
+/// The deck is cut into two packets with a binomial(n, 1/2) split, then cards
+/// drop from the bottom of either hand with probability proportional to the
+/// size of that packet. This is the model behind "seven riffles are enough".
+pub fn riffle<T, R: RngCore + ?Sized>(items: &mut Vec<T>, rng: &mut R) {
+    let n = items.len();
+    if n < 2 {
+        return;
+    }
+    let cut = binomial_half(rng, n);
+    let mut right = items.split_off(cut);
+    let mut left = std::mem::take(items);
+
+    // Drop from the bottoms (the ends of the vectors), then flip back over
+    let mut merged = Vec::with_capacity(n);
+    while !left.is_empty() || !right.is_empty() {
+        let from_left = bounded_index(rng, left.len() + right.len()) < left.len();
+        let card = if from_left { left.pop() } else { right.pop() };
+        merged.extend(card);
+    }
+    merged.reverse();
+    *items = merged;
+}
+
+/// Splits the deck into packets, breaking at each gap with probability
+/// `break_chance`, and stacks the packets back in reverse order
+fn packets<T, R: RngCore + ?Sized>(items: &mut Vec<T>, rng: &mut R, break_chance: f64) {
+    if items.len() < 2 {
+        return;
+    }
+    let mut packets: Vec<Vec<T>> = vec![];
+    let mut current = vec![];
+    for (i, item) in std::mem::take(items).into_iter().enumerate() {
+        if i > 0 && unit(rng) < break_chance {
+            packets.push(std::mem::take(&mut current));
+        }
+        current.push(item);
+    }
+    packets.push(current);
+
+    for packet in packets.into_iter().rev() {
+        items.extend(packet);
+    }
+}
+
+/// Overhand shuffle: small packets of a few cards slid from one hand to the
+/// other, which reverses their order (Pemantle's model with p = 1/3)
+pub fn overhand<T, R: RngCore + ?Sized>(items: &mut Vec<T>, rng: &mut R) {
+    packets(items, rng, 1.0 / 3.0);
+}
+
+/// Strip shuffle: like the overhand but with larger packets pulled off the
+/// top onto the table, about 6 cards each
+pub fn strip<T, R: RngCore + ?Sized>(items: &mut Vec<T>, rng: &mut R) {
+    packets(items, rng, 1.0 / 6.0);
+}
+
+/// Cuts the deck near the middle (binomial position) and completes the cut
+pub fn cut<T, R: RngCore + ?Sized>(items: &mut [T], rng: &mut R) {
+    if items.len() < 2 {
+        return;
+    }
+    let at = binomial_half(rng, items.len());
+    items.rotate_left(at);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseShuffleError(pub String);
+
+impl fmt::Display for ParseShuffleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown shuffle '{}', expected riffle, overhand, strip, cut or fisher-yates",
+            self.0
+        )
+    }
+}
+
+impl Error for ParseShuffleError {}
+
+/// One step of a shuffling routine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShuffleStep {
+    /// A perfect uniform shuffle, for comparison
+    FisherYates,
+    Riffle,
+    Overhand,
+    Strip,
+    Cut,
+}
+
+impl ShuffleStep {
+    pub fn apply<T, R: RngCore + ?Sized>(&self, items: &mut Vec<T>, rng: &mut R) {
+        match self {
+            ShuffleStep::FisherYates => fisher_yates(items, rng),
+            ShuffleStep::Riffle => riffle(items, rng),
+            ShuffleStep::Overhand => overhand(items, rng),
+            ShuffleStep::Strip => strip(items, rng),
+            ShuffleStep::Cut => cut(items, rng),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ShuffleStep::FisherYates => "fisher-yates",
+            ShuffleStep::Riffle => "riffle",
+            ShuffleStep::Overhand => "overhand",
+            ShuffleStep::Strip => "strip",
+            ShuffleStep::Cut => "cut",
+        }
+    }
+}
+
+impl fmt::Display for ShuffleStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ShuffleStep {
+    type Err = ParseShuffleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "fisher-yates" | "fy" => Ok(ShuffleStep::FisherYates),
+            "riffle" | "r" => Ok(ShuffleStep::Riffle),
+            "overhand" | "o" => Ok(ShuffleStep::Overhand),
+            "strip" | "s" => Ok(ShuffleStep::Strip),
+            "cut" | "c" => Ok(ShuffleStep::Cut),
+            other => Err(ParseShuffleError(other.to_string())),
+        }
+    }
+}
+
+/// A routine of shuffle steps applied in order, e.g. a dealer's
+/// "riffle, riffle, strip, riffle, cut"
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShuffleSequence {
+    steps: Vec<ShuffleStep>,
+}
+
+impl ShuffleSequence {
+    pub fn new() -> Self {
+        ShuffleSequence { steps: vec![] }
+    }
+
+    /// The common casino hand shuffle: riffle, riffle, strip, riffle, cut
+    pub fn casino() -> Self {
+        ShuffleSequence::new()
+            .then(ShuffleStep::Riffle)
+            .then(ShuffleStep::Riffle)
+            .then(ShuffleStep::Strip)
+            .then(ShuffleStep::Riffle)
+            .then(ShuffleStep::Cut)
+    }
+
+    pub fn then(mut self, step: ShuffleStep) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Repeats the whole routine `times` times
+    pub fn repeat(self, times: usize) -> Self {
+        let steps = self.steps.repeat(times);
+        ShuffleSequence { steps }
+    }
+
+    pub fn steps(&self) -> &[ShuffleStep] {
+        &self.steps
+    }
+
+    pub fn apply<T, R: RngCore + ?Sized>(&self, items: &mut Vec<T>, rng: &mut R) {
+        for step in &self.steps {
+            step.apply(items, rng);
+        }
+    }
+}
+
+impl From<Vec<ShuffleStep>> for ShuffleSequence {
+    fn from(steps: Vec<ShuffleStep>) -> Self {
+        ShuffleSequence { steps }
+    }
+}
+
+impl fmt::Display for ShuffleSequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<&str> = self.steps.iter().map(ShuffleStep::name).collect();
+        f.write_str(&names.join(", "))
+    }
+}
+
+impl FromStr for ShuffleSequence {
+    type Err = ParseShuffleError;
+
+    /// "riffle, riffle, strip, riffle, cut" or "r r s r c"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|step| !step.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<_>, _>>()
+            .map(ShuffleSequence::from)
+    }
+}
@@ -0,0 +1,169 @@
+use rand::RngCore;
+
+use super::models::ShuffleSequence;
+use super::seeded_rng;
+
+/// Number of rising sequences in a permutation of `0..n`.
+///
+/// `order[i]` is the original position of the card now at position `i`. A
+/// rising sequence is a maximal run of originally consecutive cards that still
+/// appear in increasing positions. A fresh deck has 1, one riffle leaves at
+/// most 2, and a uniformly random deck of n cards has (n + 1) / 2 on average.
+pub fn rising_sequences(order: &[usize]) -> usize {
+    if order.is_empty() {
+        return 0;
+    }
+    let mut position = vec![0; order.len()];
+    for (i, original) in order.iter().enumerate() {
+        position[*original] = i;
+    }
+    1 + position.windows(2).filter(|pair| pair[1] < pair[0]).count()
+}
+
+/// Pearson chi-square test of a table of observed counts against a uniform
+/// expectation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquare {
+    pub statistic: f64,
+    pub degrees_of_freedom: usize,
+    /// Chance of a statistic at least this large if the shuffle were uniform.
+    /// Values close to 0 mean the positions are clearly biased.
+    pub p_value: f64,
+}
+
+impl ChiSquare {
+    fn from_statistic(statistic: f64, degrees_of_freedom: usize) -> Self {
+        ChiSquare {
+            statistic,
+            degrees_of_freedom,
+            p_value: chi_square_upper_tail(statistic, degrees_of_freedom as f64),
+        }
+    }
+}
+
+/// Upper tail of the chi-square distribution, using the Wilson–Hilferty
+/// normal approximation (accurate to a few decimals for large degrees of freedom)
+fn chi_square_upper_tail(x: f64, k: f64) -> f64 {
+    if k <= 0.0 {
+        return 1.0;
+    }
+    let variance = 2.0 / (9.0 * k);
+    let z = ((x / k).cbrt() - (1.0 - variance)) / variance.sqrt();
+    0.5 * erfc(z / std::f64::consts::SQRT_2)
+}
+
+/// Complementary error function (Abramowitz & Stegun 7.1.26, error < 1.5e-7)
+fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs());
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let value = poly * (-x * x).exp();
+    if x >= 0.0 { value } else { 2.0 - value }
+}
+
+/// How random a deck looks after a shuffling routine, over many trials
+#[derive(Debug, Clone, PartialEq)]
+pub struct RandomnessReport {
+    pub cards: usize,
+    pub trials: usize,
+    pub mean_rising_sequences: f64,
+    /// What a perfectly random deck averages: (n + 1) / 2
+    pub expected_rising_sequences: f64,
+    /// Where each card ends up, compared with a uniform spread over positions
+    pub position_bias: ChiSquare,
+}
+
+/// Shuffles a fresh deck of `cards` cards with `sequence`, `trials` times, and
+/// measures rising sequences and position bias.
+///
+/// The same `seed` always produces the same report.
+pub fn measure(
+    sequence: &ShuffleSequence,
+    cards: usize,
+    trials: usize,
+    seed: u64,
+) -> RandomnessReport {
+    let mut rng = seeded_rng(seed);
+    measure_with(sequence, cards, trials, &mut rng)
+}
+
+pub fn measure_with<R: RngCore + ?Sized>(
+    sequence: &ShuffleSequence,
+    cards: usize,
+    trials: usize,
+    rng: &mut R,
+) -> RandomnessReport {
+    // counts[card * cards + position]
+    let mut counts = vec![0u64; cards * cards];
+    let mut rising_total = 0;
+
+    for _ in 0..trials {
+        let mut order: Vec<usize> = (0..cards).collect();
+        sequence.apply(&mut order, rng);
+        rising_total += rising_sequences(&order);
+        for (position, card) in order.iter().enumerate() {
+            counts[card * cards + position] += 1;
+        }
+    }
+
+    RandomnessReport {
+        cards,
+        trials,
+        mean_rising_sequences: rising_total as f64 / trials.max(1) as f64,
+        expected_rising_sequences: (cards as f64 + 1.0) / 2.0,
+        position_bias: position_chi_square(&counts, cards, trials),
+    }
+}
+
+/// Chi-square of a cards x positions table where every cell expects
+/// `trials / cards` hits.
+///
+/// Every row and every column adds up to `trials`, leaving (n - 1)^2 degrees
+/// of freedom. Each trial is a permutation rather than n independent draws,
+/// which makes the raw Pearson sum n / (n - 1) times a chi-square (it
+/// averages n(n - 1)), so it is scaled back by (n - 1) / n.
+fn position_chi_square(counts: &[u64], cards: usize, trials: usize) -> ChiSquare {
+    if cards < 2 || trials == 0 {
+        return ChiSquare::from_statistic(0.0, 0);
+    }
+    let n = cards as f64;
+    let expected = trials as f64 / n;
+    let pearson: f64 = counts
+        .iter()
+        .map(|observed| {
+            let diff = *observed as f64 - expected;
+            diff * diff / expected
+        })
+        .sum();
+    ChiSquare::from_statistic(pearson * (n - 1.0) / n, (cards - 1) * (cards - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shuffle::{ShuffleSequence, ShuffleStep, seeded_rng};
+
+    #[test]
+    fn uniform_shuffles_average_the_degrees_of_freedom() {
+        let uniform = ShuffleSequence::new().then(ShuffleStep::FisherYates);
+        let mut rng = seeded_rng(1);
+        let runs = 200;
+        let mut total = 0.0;
+        for _ in 0..runs {
+            let report = measure_with(&uniform, 6, 600, &mut rng);
+            assert_eq!(report.position_bias.degrees_of_freedom, 25);
+            total += report.position_bias.statistic;
+        }
+        // The mean of 200 runs has a standard error of 0.5
+        let mean = total / runs as f64;
+        assert!((mean - 25.0).abs() < 2.0, "mean statistic {}", mean);
+    }
+
+    #[test]
+    fn a_single_riffle_is_clearly_biased() {
+        let riffle = ShuffleSequence::new().then(ShuffleStep::Riffle);
+        let report = measure_with(&riffle, 52, 2000, &mut seeded_rng(1));
+        assert!(report.position_bias.p_value < 1e-6);
+    }
+}
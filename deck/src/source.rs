@@ -0,0 +1,44 @@
+use crate::card::Card;
+use crate::deck::DealError;
+use crate::hand::Hand;
+
+/// Anything cards can be drawn from: a single `Deck` or a multi-deck `Shoe`.
+///
+/// Game code that only needs to draw and deal should take a `CardSource` so it
+/// works with either.
+pub trait CardSource {
+    /// Takes the top card, `None` when there is nothing left to draw
+    fn draw_one(&mut self) -> Option<Card>;
+
+    fn remaining(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Deals exactly `num_cards`, or nothing at all
+    fn deal(&mut self, num_cards: usize) -> Result<Hand, DealError> {
+        let remaining = self.remaining();
+        if num_cards > remaining {
+            return Err(DealError::NotEnoughCards {
+                requested: num_cards,
+                remaining,
+            });
+        }
+        Ok(self.deal_up_to(num_cards))
+    }
+
+    /// Deals `num_cards`, or fewer if the source runs dry first. A `Deck`
+    /// stops after what `remaining` reports, a `Shoe` reshuffles its discards
+    /// and keeps going.
+    fn deal_up_to(&mut self, num_cards: usize) -> Hand {
+        let mut hand = Hand::new();
+        for _ in 0..num_cards {
+            match self.draw_one() {
+                Some(card) => hand.add(card),
+                None => break,
+            }
+        }
+        hand
+    }
+}
@@ -0,0 +1,459 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use rand_chacha::ChaCha8Rng;
+
+use crate::card::Card;
+use crate::deck::Deck;
+use crate::shuffle::{fisher_yates, seeded_rng};
+
+/// What happens when both cards turned up have the same rank
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieRule {
+    /// Each player puts `face_down` cards down then turns up another, until
+    /// someone wins the lot
+    War { face_down: usize },
+    /// Each player takes their own card back to the bottom of their pile
+    Split,
+}
+
+/// What happens when a player can't put down a full war
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShortHanded {
+    /// They lose the game on the spot
+    Loses,
+    /// They put down what they can and turn up their last card
+    PlaysLastCard,
+}
+
+/// How a player adds the cards they won to the bottom of their pile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pickup {
+    /// In the order they were played. Fully deterministic, so a game can
+    /// cycle forever.
+    InOrder,
+    /// Shuffled first, which breaks cycles
+    Shuffled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WarRules {
+    pub tie: TieRule,
+    pub short_handed: ShortHanded,
+    pub pickup: Pickup,
+    /// Games still going after this many rounds are stopped
+    pub max_rounds: usize,
+}
+
+impl Default for WarRules {
+    /// Three cards down in a war, short players play their last card, won
+    /// cards go under in play order
+    fn default() -> Self {
+        WarRules {
+            tie: TieRule::War { face_down: 3 },
+            short_handed: ShortHanded::PlaysLastCard,
+            pickup: Pickup::InOrder,
+            max_rounds: 10_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarEnding {
+    /// `player` (0 or 1) holds every card
+    Winner(usize),
+    /// Both players ran out during the same war
+    Draw,
+    /// The piles came back to a position seen `cycle` rounds earlier and
+    /// would repeat forever
+    Loop {
+        cycle: usize,
+    },
+    RoundLimit,
+}
+
+/// How one game went
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WarGame {
+    pub ending: WarEnding,
+    pub rounds: usize,
+    pub wars: usize,
+    /// Most wars in a row within one round (a double war is 2)
+    pub longest_war: usize,
+}
+
+/// A two-player game of War, each player's pile being a `Deck` turned up from
+/// the top
+#[derive(Debug, Clone)]
+pub struct War {
+    piles: [Deck; 2],
+    rules: WarRules,
+    rng: ChaCha8Rng,
+    rounds: usize,
+    wars: usize,
+    longest_war: usize,
+}
+
+impl War {
+    /// Shuffles a standard deck from `seed` and deals it out one card each
+    pub fn deal(rules: WarRules, seed: u64) -> Self {
+        War::deal_with(rules, seeded_rng(seed))
+    }
+
+    /// Deals game `index` of `simulate(rules, _, seed)`, to replay it
+    pub fn deal_game(rules: WarRules, seed: u64, index: usize) -> Self {
+        let mut rng = seeded_rng(seed);
+        rng.set_stream(index as u64);
+        War::deal_with(rules, rng)
+    }
+
+    fn deal_with(rules: WarRules, mut rng: ChaCha8Rng) -> Self {
+        let mut deck = Deck::new();
+        deck.shufle_with(&mut rng);
+        let mut piles = [Deck::from_cards(vec![]), Deck::from_cards(vec![])];
+        let mut seat = 0;
+        while let Some(card) = deck.draw_one() {
+            piles[seat].put_on_bottom(card);
+            seat = 1 - seat;
+        }
+        War {
+            piles,
+            rules,
+            rng,
+            rounds: 0,
+            wars: 0,
+            longest_war: 0,
+        }
+    }
+
+    /// Cards left in each player's pile
+    pub fn pile_sizes(&self) -> [usize; 2] {
+        [self.piles[0].remaining(), self.piles[1].remaining()]
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /// The RNG that shuffles won cards, for `SavedGame::with_rng`
+    pub fn rng(&self) -> &ChaCha8Rng {
+        &self.rng
+    }
+
+    /// Plays one round: a battle plus any wars it leads to. Returns how the
+    /// game ended if it did.
+    pub fn play_round(&mut self) -> Option<WarEnding> {
+        self.rounds += 1;
+        let mut pot: Vec<Card> = vec![];
+        let mut wars = 0;
+        loop {
+            let (a, b) = match (self.piles[0].draw_one(), self.piles[1].draw_one()) {
+                (Some(a), Some(b)) => (a, b),
+                // Only happens at the start of a round, a war checks first
+                (None, None) => return Some(WarEnding::Draw),
+                (None, _) => return Some(WarEnding::Winner(1)),
+                (_, None) => return Some(WarEnding::Winner(0)),
+            };
+            pot.push(a);
+            pot.push(b);
+
+            let winner = match a.rank.cmp(&b.rank) {
+                Ordering::Greater => 0,
+                Ordering::Less => 1,
+                Ordering::Equal => {
+                    let face_down = match self.rules.tie {
+                        TieRule::Split => {
+                            // Back to their owners, nothing else in the pot
+                            // can be on the table
+                            debug_assert_eq!(pot.len(), 2);
+                            self.piles[0].put_on_bottom(a);
+                            self.piles[1].put_on_bottom(b);
+                            return self.finished();
+                        }
+                        TieRule::War { face_down } => face_down,
+                    };
+                    wars += 1;
+                    self.wars += 1;
+                    self.longest_war = self.longest_war.max(wars);
+                    if let Some(ending) = self.lay_down(face_down, &mut pot) {
+                        return Some(ending);
+                    }
+                    continue;
+                }
+            };
+
+            if self.rules.pickup == Pickup::Shuffled {
+                fisher_yates(&mut pot, &mut self.rng);
+            }
+            for card in pot {
+                self.piles[winner].put_on_bottom(card);
+            }
+            return self.finished();
+        }
+    }
+
+    /// Plays until the game ends
+    pub fn play(mut self) -> WarGame {
+        // Loops can only be spotted when nothing is random
+        let mut seen: HashMap<(Vec<Card>, Vec<Card>), usize> = HashMap::new();
+        let ending = loop {
+            if let Some(ending) = self.play_round() {
+                break ending;
+            }
+            if self.rounds >= self.rules.max_rounds {
+                break WarEnding::RoundLimit;
+            }
+            if self.rules.pickup == Pickup::InOrder
+                && let Some(earlier) = seen.insert(self.position(), self.rounds)
+            {
+                break WarEnding::Loop {
+                    cycle: self.rounds - earlier,
+                };
+            }
+        };
+        WarGame {
+            ending,
+            rounds: self.rounds,
+            wars: self.wars,
+            longest_war: self.longest_war,
+        }
+    }
+
+    /// Both players put cards face down for a war, keeping one to turn up
+    fn lay_down(&mut self, face_down: usize, pot: &mut Vec<Card>) -> Option<WarEnding> {
+        let short = |pile: &Deck| pile.remaining() < face_down + 1;
+        let short_players = [short(&self.piles[0]), short(&self.piles[1])];
+        match (self.rules.short_handed, short_players) {
+            (ShortHanded::Loses, [true, true]) => {
+                let sizes = self.pile_sizes();
+                return Some(match sizes[0].cmp(&sizes[1]) {
+                    Ordering::Less => WarEnding::Winner(1),
+                    Ordering::Greater => WarEnding::Winner(0),
+                    Ordering::Equal => WarEnding::Draw,
+                });
+            }
+            (ShortHanded::Loses, [true, false]) => return Some(WarEnding::Winner(1)),
+            (ShortHanded::Loses, [false, true]) => return Some(WarEnding::Winner(0)),
+            _ => {}
+        }
+
+        match (self.piles[0].is_empty(), self.piles[1].is_empty()) {
+            (true, true) => return Some(WarEnding::Draw),
+            (true, false) => return Some(WarEnding::Winner(1)),
+            (false, true) => return Some(WarEnding::Winner(0)),
+            (false, false) => {}
+        }
+        for pile in &mut self.piles {
+            let count = face_down.min(pile.remaining() - 1);
+            pot.extend(pile.deal_up_to(count));
+        }
+        None
+    }
+
+    fn finished(&self) -> Option<WarEnding> {
+        match self.pile_sizes() {
+            [0, _] => Some(WarEnding::Winner(1)),
+            [_, 0] => Some(WarEnding::Winner(0)),
+            _ => None,
+        }
+    }
+
+    fn position(&self) -> (Vec<Card>, Vec<Card>) {
+        (self.piles[0].cards.clone(), self.piles[1].cards.clone())
+    }
+}
+
+/// Results of many games with the same rules
+#[derive(Debug, Clone, PartialEq)]
+pub struct WarStats {
+    pub games: usize,
+    pub wins: [usize; 2],
+    pub draws: usize,
+    pub loops: usize,
+    pub round_limits: usize,
+    /// Over games that had a winner
+    pub mean_rounds: f64,
+    pub median_rounds: usize,
+    pub min_rounds: usize,
+    pub max_rounds: usize,
+    pub mean_wars: f64,
+    /// Games with a winner by length, in buckets of `bucket_size` rounds
+    /// keyed by their first round
+    pub histogram: BTreeMap<usize, usize>,
+    pub bucket_size: usize,
+}
+
+/// Plays `games` games. Game `i` is dealt from its own RNG stream of `seed`,
+/// so any game can be replayed with `War::deal_game` and results don't depend
+/// on the game count.
+pub fn simulate(rules: WarRules, games: usize, seed: u64) -> WarStats {
+    let records: Vec<WarGame> = (0..games)
+        .map(|i| War::deal_game(rules, seed, i).play())
+        .collect();
+    // Around 20 buckets, in multiples of 10 rounds
+    let longest = records.iter().map(|game| game.rounds).max().unwrap_or(0);
+    let bucket_size = longest.div_ceil(200).max(1) * 10;
+    WarStats::from_games(&records, bucket_size)
+}
+
+impl WarStats {
+    pub fn from_games(records: &[WarGame], bucket_size: usize) -> Self {
+        let mut stats = WarStats {
+            games: records.len(),
+            wins: [0, 0],
+            draws: 0,
+            loops: 0,
+            round_limits: 0,
+            mean_rounds: 0.0,
+            median_rounds: 0,
+            min_rounds: 0,
+            max_rounds: 0,
+            mean_wars: 0.0,
+            histogram: BTreeMap::new(),
+            bucket_size,
+        };
+        let mut lengths = vec![];
+        let mut wars = 0;
+        for record in records {
+            wars += record.wars;
+            match record.ending {
+                WarEnding::Winner(player) => {
+                    stats.wins[player] += 1;
+                    lengths.push(record.rounds);
+                    *stats
+                        .histogram
+                        .entry(record.rounds / bucket_size * bucket_size)
+                        .or_default() += 1;
+                }
+                WarEnding::Draw => stats.draws += 1,
+                WarEnding::Loop { .. } => stats.loops += 1,
+                WarEnding::RoundLimit => stats.round_limits += 1,
+            }
+        }
+
+        lengths.sort_unstable();
+        if !lengths.is_empty() {
+            stats.mean_rounds = lengths.iter().sum::<usize>() as f64 / lengths.len() as f64;
+            stats.median_rounds = lengths[lengths.len() / 2];
+            stats.min_rounds = lengths[0];
+            stats.max_rounds = lengths[lengths.len() - 1];
+        }
+        if !records.is_empty() {
+            stats.mean_wars = wars as f64 / records.len() as f64;
+        }
+        stats
+    }
+}
+
+impl fmt::Display for WarStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} games: player 1 won {}, player 2 won {}, {} draws, {} loops, {} stopped",
+            self.games, self.wins[0], self.wins[1], self.draws, self.loops, self.round_limits
+        )?;
+        writeln!(
+            f,
+            "Rounds: mean {:.1}, median {}, min {}, max {}; {:.1} wars per game",
+            self.mean_rounds, self.median_rounds, self.min_rounds, self.max_rounds, self.mean_wars
+        )?;
+        // Every bucket has at least one game, so `tallest` isn't zero here
+        let tallest = self.histogram.values().copied().max().unwrap_or(1);
+        for (start, count) in &self.histogram {
+            writeln!(
+                f,
+                "{:>5}-{:<5} {:>6} {}",
+                start,
+                start + self.bucket_size - 1,
+                count,
+                "#".repeat(count * 50 / tallest)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notation::parse_cards;
+
+    /// A game from two piles listed top card first
+    fn game(rules: WarRules, first: &str, second: &str) -> War {
+        let pile = |text: &str| {
+            let mut cards = parse_cards(text).unwrap();
+            cards.reverse();
+            Deck::from_cards(cards)
+        };
+        War {
+            piles: [pile(first), pile(second)],
+            rules,
+            rng: seeded_rng(0),
+            rounds: 0,
+            wars: 0,
+            longest_war: 0,
+        }
+    }
+
+    #[test]
+    fn split_ties_go_back_to_their_owners() {
+        let rules = WarRules {
+            tie: TieRule::Split,
+            ..WarRules::default()
+        };
+        let mut war = game(rules, "5c 9h", "5d 2s");
+        assert_eq!(war.play_round(), None);
+        assert_eq!(war.pile_sizes(), [2, 2]);
+        assert_eq!(war.piles[0].cards, parse_cards("5c 9h").unwrap());
+        assert_eq!(war.piles[1].cards, parse_cards("5d 2s").unwrap());
+        assert_eq!(war.wars, 0);
+    }
+
+    #[test]
+    fn short_of_cards_for_a_war_loses_under_loses() {
+        let rules = WarRules {
+            short_handed: ShortHanded::Loses,
+            ..WarRules::default()
+        };
+        // Player 2 has an Ace to turn up, but only one card after the tie
+        let mut war = game(rules, "7c 2c 3c 4c 5c", "7d Ah");
+        assert_eq!(war.play_round(), Some(WarEnding::Winner(0)));
+    }
+
+    #[test]
+    fn short_of_cards_for_a_war_plays_the_last_card() {
+        let mut war = game(WarRules::default(), "7c 2c 3c 4c Kc", "7d 2d Ah");
+        assert_eq!(war.play_round(), Some(WarEnding::Winner(1)));
+        assert_eq!(war.pile_sizes(), [0, 8]);
+    }
+
+    #[test]
+    fn in_order_pickup_finds_a_cycle() {
+        // Kc takes a Two, Kd takes the other, and the piles come back around
+        // every four rounds
+        let game = game(WarRules::default(), "Kc 2d", "2c Kd").play();
+        assert_eq!(game.ending, WarEnding::Loop { cycle: 4 });
+        assert_eq!(game.rounds, 5);
+    }
+
+    #[test]
+    fn simulated_games_depend_only_on_seed_and_index() {
+        let rules = WarRules {
+            pickup: Pickup::Shuffled,
+            ..WarRules::default()
+        };
+        let games: Vec<WarGame> = (0..12)
+            .map(|i| War::deal_game(rules, 7, i).play())
+            .collect();
+        assert_ne!(games[0], games[1]);
+        for count in [1, 5, 12] {
+            let stats = simulate(rules, count, 7);
+            assert_eq!(
+                stats,
+                WarStats::from_games(&games[..count], stats.bucket_size)
+            );
+        }
+        assert_eq!(simulate(rules, 12, 7), simulate(rules, 12, 7));
+    }
+}
@@ -0,0 +1,43 @@
+// Result handling patterns, runnable on their own:
+// cargo run --example error_handling
+// `-> ()` is spelled out to show what a function without a value returns
+#![allow(clippy::unused_unit)]
+
+use std::io::Error;
+
+fn main() {
+    basic_error_handling();
+}
+
+fn basic_error_handling() -> () {
+    // Pattern 1: Match with both value and error
+    // Use when you need to access the successful value
+    match divide(5.0, 0.0) {
+        Ok(value) => println!("{}", value),
+        Err(err) => println!("{}", err),
+    }
+
+    // Pattern 2: Match with void value (using ..)
+    // Use when you only care about success/failure, not the actual value
+    // The .. pattern ignores the content when we don't need it
+    match validate_email(String::from("asdf@asdf.com")) {
+        Ok(..) => println!("email is valid"),
+        Err(err) => println!("{}", err),
+    }
+}
+
+fn validate_email(email: String) -> Result<(), Error> {
+    if email.contains("@") {
+        Ok(()) // returning an empty Tuple
+    } else {
+        Err(Error::other("email must have a @"))
+    }
+}
+
+fn divide(a: f64, b: f64) -> Result<f64, Error> {
+    if b == 0.0 {
+        Err(Error::other("can't divide by 0"))
+    } else {
+        Ok(a / b)
+    }
+}
@@ -0,0 +1,350 @@
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+
+use regex::RegexBuilder;
+
+use crate::entry::{Level, ParseError};
+use crate::output::Format;
+use crate::search::{Pattern, Search};
+use crate::window::{TimeRange, parse_clock, parse_duration};
+
+pub const USAGE: &str = "\
+Usage: logs [OPTIONS] [FILE]...
+
+Reads log files (or stdin when no FILE is given, or for '-') and prints the
+entries that match.
+
+Options:
+  -o, --output FILE     write matches to FILE instead of stdout
+  -f, --format FORMAT   raw (default), json, ndjson or csv; the structured
+                        formats have level, time, message, source (the
+                        FILE) and line fields
+  -l, --level LEVEL     keep LEVEL and anything more severe (default: ERROR)
+      --only LEVELS     keep exactly these levels, e.g. --only INFO,FATAL
+      --since TIME      keep entries at or after TIME (HH:MM or HH:MM:SS)
+      --until TIME      keep entries at or before TIME; HH:MM keeps that
+                        whole minute
+      --last DURATION   keep entries from the last DURATION before the
+                        newest entry, e.g. 90s, 15m, 2h (plain numbers are
+                        minutes)
+  -s, --search TEXT     keep entries whose message contains TEXT
+  -e, --regex PATTERN   keep entries whose message matches PATTERN
+  -i, --ignore-case     match --search and --regex regardless of case
+  -v, --invert          keep entries whose message doesn't match instead
+      --color WHEN      highlight matches: auto (default), always or never
+  -h, --help            show this help
+
+Levels: TRACE DEBUG INFO WARNING ERROR FATAL
+
+--search and --regex can be repeated; a message matches if any of them does.
+Patterns use the syntax of the Rust regex crate.
+
+Log times have no date: a --since later than --until wraps past midnight,
+and --last doesn't reach back before midnight.
+
+Exit status: 0 if any entry matched, 1 if none did, 2 on errors.";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Input::Stdin => f.write_str("<stdin>"),
+            Input::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LevelFilter {
+    AtLeast(Level),
+    Only(Vec<Level>),
+}
+
+impl LevelFilter {
+    pub fn matches(&self, level: Level) -> bool {
+        match self {
+            LevelFilter::AtLeast(min) => level >= *min,
+            LevelFilter::Only(levels) => levels.contains(&level),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    // Only when writing to a terminal, and NO_COLOR isn't set
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub inputs: Vec<Input>,
+    // None writes to stdout
+    pub output: Option<PathBuf>,
+    pub format: Format,
+    pub levels: LevelFilter,
+    pub range: TimeRange,
+    // Length of the --last window in seconds
+    pub last: Option<u32>,
+    pub search: Search,
+    pub color: ColorChoice,
+}
+
+// What the command line asks for
+#[derive(Debug, Clone)]
+pub enum Command {
+    Run(Options),
+    Help,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UsageError {
+    UnknownOption(String),
+    MissingValue(String),
+    BadLevel(ParseError),
+    BadTime(String),
+    BadDuration(String),
+    BadRegex(String, regex::Error),
+    BadColor(String),
+    BadFormat(String),
+    // --invert without anything to invert
+    NothingToInvert,
+    // --level and --only both given
+    ConflictingLevels,
+}
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UsageError::UnknownOption(option) => write!(f, "unknown option '{}'", option),
+            UsageError::MissingValue(option) => write!(f, "{} needs a value", option),
+            UsageError::BadLevel(err) => write!(f, "{}", err),
+            UsageError::BadTime(time) => {
+                write!(f, "bad time '{}', expected HH:MM or HH:MM:SS", time)
+            }
+            UsageError::BadDuration(duration) => {
+                write!(
+                    f,
+                    "bad duration '{}', expected e.g. 90s, 15m or 2h",
+                    duration
+                )
+            }
+            UsageError::BadRegex(pattern, err) => write!(f, "bad regex '{}': {}", pattern, err),
+            UsageError::BadColor(when) => {
+                write!(f, "bad --color '{}', expected auto, always or never", when)
+            }
+            UsageError::BadFormat(format) => {
+                write!(
+                    f,
+                    "bad --format '{}', expected raw, json, ndjson or csv",
+                    format
+                )
+            }
+            UsageError::NothingToInvert => f.write_str("--invert needs --search or --regex"),
+            UsageError::ConflictingLevels => f.write_str("--level and --only can't be combined"),
+        }
+    }
+}
+
+impl Error for UsageError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            UsageError::BadLevel(err) => Some(err),
+            UsageError::BadRegex(_, err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for UsageError {
+    fn from(err: ParseError) -> Self {
+        UsageError::BadLevel(err)
+    }
+}
+
+// Parses the arguments after the program name
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, UsageError> {
+    let mut inputs = vec![];
+    let mut output = None;
+    let mut format = Format::Raw;
+    let mut levels = None;
+    let mut range = TimeRange::default();
+    let mut last = None;
+    // Regexes are compiled at the end, once -i is known
+    let mut texts = vec![];
+    let mut regexes = vec![];
+    let mut ignore_case = false;
+    let mut invert = false;
+    let mut color = ColorChoice::Auto;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |option: &str| {
+            args.next()
+                .ok_or_else(|| UsageError::MissingValue(option.to_string()))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-o" | "--output" => output = Some(PathBuf::from(value(&arg)?)),
+            "-f" | "--format" => {
+                let name = value(&arg)?;
+                format = Format::from_name(&name).ok_or(UsageError::BadFormat(name))?;
+            }
+            "-l" | "--level" => {
+                let level = value(&arg)?.to_uppercase().parse()?;
+                set_levels(&mut levels, LevelFilter::AtLeast(level))?;
+            }
+            "--only" => {
+                let only = value(&arg)?
+                    .split(',')
+                    .map(|level| level.trim().to_uppercase().parse())
+                    .collect::<Result<_, _>>()?;
+                set_levels(&mut levels, LevelFilter::Only(only))?;
+            }
+            "--since" | "--until" => {
+                let text = value(&arg)?;
+                let end = arg == "--until";
+                let time = parse_clock(&text, end).ok_or(UsageError::BadTime(text))?;
+                if end {
+                    range.to = Some(time);
+                } else {
+                    range.from = Some(time);
+                }
+            }
+            "--last" => {
+                let text = value(&arg)?;
+                last = Some(parse_duration(&text).ok_or(UsageError::BadDuration(text))?);
+            }
+            "-s" | "--search" => texts.push(value(&arg)?),
+            "-e" | "--regex" => regexes.push(value(&arg)?),
+            "-i" | "--ignore-case" => ignore_case = true,
+            "-v" | "--invert" => invert = true,
+            "--color" => {
+                color = match value(&arg)?.as_str() {
+                    "auto" => ColorChoice::Auto,
+                    "always" => ColorChoice::Always,
+                    "never" => ColorChoice::Never,
+                    other => return Err(UsageError::BadColor(other.to_string())),
+                }
+            }
+            "-" => inputs.push(Input::Stdin),
+            option if option.starts_with('-') => {
+                return Err(UsageError::UnknownOption(option.to_string()));
+            }
+            path => inputs.push(Input::File(PathBuf::from(path))),
+        }
+    }
+
+    let mut patterns: Vec<Pattern> = texts.into_iter().map(Pattern::Text).collect();
+    for pattern in regexes {
+        match RegexBuilder::new(&pattern)
+            .case_insensitive(ignore_case)
+            .build()
+        {
+            Ok(regex) => patterns.push(Pattern::Regex(regex)),
+            Err(err) => return Err(UsageError::BadRegex(pattern, err)),
+        }
+    }
+    if invert && patterns.is_empty() {
+        return Err(UsageError::NothingToInvert);
+    }
+
+    if inputs.is_empty() {
+        inputs.push(Input::Stdin);
+    }
+    Ok(Command::Run(Options {
+        inputs,
+        output,
+        format,
+        levels: levels.unwrap_or(LevelFilter::AtLeast(Level::Error)),
+        range,
+        last,
+        search: Search {
+            patterns,
+            ignore_case,
+            invert,
+        },
+        color,
+    }))
+}
+
+fn set_levels(levels: &mut Option<LevelFilter>, filter: LevelFilter) -> Result<(), UsageError> {
+    if levels.is_some() {
+        return Err(UsageError::ConflictingLevels);
+    }
+    *levels = Some(filter);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, UsageError> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn options(args: &[&str]) -> Options {
+        match parse(args) {
+            Ok(Command::Run(options)) => options,
+            other => panic!("{:?} gave {:?}", args, other),
+        }
+    }
+
+    #[test]
+    fn level_and_only_conflict() {
+        assert_eq!(
+            parse(&["--level", "warning", "--only", "INFO"]).err(),
+            Some(UsageError::ConflictingLevels)
+        );
+        assert_eq!(
+            options(&["--only", "info, fatal"]).levels,
+            LevelFilter::Only(vec![Level::Info, Level::Fatal])
+        );
+    }
+
+    #[test]
+    fn invert_needs_a_pattern() {
+        assert_eq!(
+            parse(&["-v", "app.log"]).err(),
+            Some(UsageError::NothingToInvert)
+        );
+        assert!(options(&["-v", "-s", "disk"]).search.invert);
+    }
+
+    #[test]
+    fn an_option_at_the_end_is_missing_its_value() {
+        assert_eq!(
+            parse(&["app.log", "--since"]).err(),
+            Some(UsageError::MissingValue("--since".into()))
+        );
+        assert_eq!(
+            parse(&["-o"]).err(),
+            Some(UsageError::MissingValue("-o".into()))
+        );
+    }
+
+    #[test]
+    fn reads_stdin_for_a_dash_or_when_no_file_is_given() {
+        assert_eq!(options(&[]).inputs, [Input::Stdin]);
+        assert_eq!(
+            options(&["a.log", "-", "b.log"]).inputs,
+            [
+                Input::File("a.log".into()),
+                Input::Stdin,
+                Input::File("b.log".into())
+            ]
+        );
+        assert_eq!(
+            options(&["-l", "info"]).levels,
+            LevelFilter::AtLeast(Level::Info)
+        );
+    }
+}
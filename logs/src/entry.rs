@@ -0,0 +1,192 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+// Ordered by severity, so `level >= Level::Warning` keeps warnings and worse
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warning,
+    Error,
+    Fatal,
+}
+
+impl Level {
+    pub const ALL: [Level; 6] = [
+        Level::Trace,
+        Level::Debug,
+        Level::Info,
+        Level::Warning,
+        Level::Error,
+        Level::Fatal,
+    ];
+
+    // The word used in log files: "WARNING"
+    pub fn name(&self) -> &'static str {
+        match self {
+            Level::Trace => "TRACE",
+            Level::Debug => "DEBUG",
+            Level::Info => "INFO",
+            Level::Warning => "WARNING",
+            Level::Error => "ERROR",
+            Level::Fatal => "FATAL",
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Level {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Level::ALL
+            .into_iter()
+            .find(|level| level.name() == s)
+            .ok_or_else(|| ParseError::UnknownLevel(s.to_string()))
+    }
+}
+
+// Time of day as written in the logs, HH:MM:SS
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Time {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl Time {
+    pub fn new(hour: u8, minute: u8, second: u8) -> Option<Time> {
+        if hour < 24 && minute < 60 && second < 60 {
+            Some(Time {
+                hour,
+                minute,
+                second,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn seconds_since_midnight(&self) -> u32 {
+        self.hour as u32 * 3600 + self.minute as u32 * 60 + self.second as u32
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+    }
+}
+
+impl FromStr for Time {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bad = || ParseError::BadTime(s.to_string());
+        let parts: Vec<&str> = s.split(':').collect();
+        // Exactly two digits each, so "1:2:3" or "+1:00:00" are rejected
+        if parts.len() != 3
+            || parts
+                .iter()
+                .any(|part| part.len() != 2 || !part.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return Err(bad());
+        }
+        let number = |part: &str| part.parse::<u8>().map_err(|_| bad());
+        Time::new(number(parts[0])?, number(parts[1])?, number(parts[2])?).ok_or_else(bad)
+    }
+}
+
+// One line of a log file: "ERROR 14:33:45 Failed to connect to the database."
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    pub level: Level,
+    pub time: Time,
+    pub message: String,
+}
+
+// Display gives back the line the entry was parsed from
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.level, self.time)?;
+        if !self.message.is_empty() {
+            write!(f, " {}", self.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for LogEntry {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().splitn(3, ' ');
+        let level = match parts.next() {
+            Some("") | None => return Err(ParseError::Empty),
+            Some(level) => level.parse()?,
+        };
+        let time = parts.next().ok_or(ParseError::MissingTime)?.parse()?;
+        let message = parts.next().unwrap_or("").trim().to_string();
+        Ok(LogEntry {
+            level,
+            time,
+            message,
+        })
+    }
+}
+
+// An entry, the input it was read from (an index into the inputs) and its
+// line in that input, counting from 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub source: usize,
+    pub line: usize,
+    pub entry: LogEntry,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    UnknownLevel(String),
+    MissingTime,
+    BadTime(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Empty => f.write_str("empty line"),
+            ParseError::UnknownLevel(level) => write!(f, "unknown level '{}'", level),
+            ParseError::MissingTime => f.write_str("missing time"),
+            ParseError::BadTime(time) => write!(f, "bad time '{}', expected HH:MM:SS", time),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+// A ParseError with the line it happened on, counting from 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    pub line: usize,
+    pub error: ParseError,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+impl Error for LineError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
@@ -1,88 +1,146 @@
-use std::{fs, io::Error};
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Error, ErrorKind, IsTerminal, Write};
+use std::path::Path;
+use std::process::ExitCode;
 
-fn main() -> Result<(), Error> {
-    basic_error_handling();
-    nested_match_statements();
-    expects();
+use cli::{ColorChoice, Command, Input, Options, USAGE, parse_args};
+use output::{Format, RecordWriter};
+use stream::LogReader;
+use window::LastWindow;
 
-    // ? is the Try operator
-    // Idiomatic Rust - concise but still propagates errors properly
-    let text = fs::read_to_string("logs.txt")?;
-    let error_logs = extract_errors(text.as_str());
-    fs::write("errors.txt", error_logs.join("\n"))?;
+mod cli;
+mod entry;
+mod output;
+mod search;
+mod stream;
+mod window;
 
-    Ok(())
-}
+fn main() -> ExitCode {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(Command::Run(options)) => options,
+        Ok(Command::Help) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("logs: {}\n\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
 
-// don't handle panic
-fn expects() -> () {
-    let text = fs::read_to_string("logs.txt").expect("failed to read logs.txt");
-    let error_logs = extract_errors(text.as_str());
-    fs::write("errors_expect.txt", error_logs.join("\n"))
-        .expect("failed to write errors_expect.txt");
+    // Match instead of ? so each outcome gets its own exit code
+    match run(&options) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        // The reader went away (`logs big.log | head`), nothing left to do
+        Err(err) if err.kind() == ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("logs: {}", err);
+            ExitCode::from(2)
+        }
+    }
 }
 
-// handles panic but nested / hard to read
-fn nested_match_statements() -> () {
-    let mut error_logs: Vec<String> = vec![];
-    match fs::read_to_string("logs.txt") {
-        Ok(text) => {
-            error_logs = extract_errors(text.as_str());
+// Streams every input through the filter into the output, one line at a time.
+// Returns whether any entry matched.
+fn run(options: &Options) -> Result<bool, Error> {
+    if let Some(path) = &options.output {
+        refuse_input_as_output(path, &options.inputs)?;
+    }
+    let out: BufWriter<Box<dyn Write>> = BufWriter::new(match &options.output {
+        Some(path) => Box::new(File::create(path).map_err(|err| with_path(path, err))?),
+        None => Box::new(io::stdout().lock()),
+    });
 
-            match fs::write("errors_nested.txt", error_logs.join("\n")) {
-                Ok(..) => println!("Wrote errors_nested.txt"),
-                Err(error) => println!("Writing of errors_nested.txt failed: {}", error),
+    let colored = options.format == Format::Raw
+        && !options.search.is_empty()
+        && match options.color {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                options.output.is_none()
+                    && io::stdout().is_terminal()
+                    && env::var_os("NO_COLOR").is_none()
+            }
+        };
+    let sources: Vec<String> = options.inputs.iter().map(Input::to_string).collect();
+    let mut writer = RecordWriter::new(
+        out,
+        options.format,
+        &sources,
+        colored.then_some(&options.search),
+    );
+
+    // With --last nothing can be written until the newest entry is known
+    let mut window = options.last.map(LastWindow::new);
+    let mut found = false;
+    for (source, input) in options.inputs.iter().enumerate() {
+        // ? is the Try operator: the first unreadable input stops the run
+        let reader = open_input(input)?;
+        for parsed in LogReader::new(reader, source) {
+            match parsed.map_err(|err| Error::new(err.kind(), format!("{}: {}", input, err)))? {
+                Ok(record) => {
+                    let entry = &record.entry;
+                    let keep = options.levels.matches(entry.level)
+                        && options.range.contains(entry.time)
+                        && options.search.matches(&entry.message);
+                    match &mut window {
+                        Some(window) if keep => window.push(record),
+                        Some(window) => window.see(entry.time),
+                        None if keep => {
+                            found = true;
+                            writer.write(&record)?;
+                        }
+                        None => {}
+                    }
+                }
+                // Malformed lines are reported but don't stop the run
+                Err(error) => eprintln!("logs: {}: {}", input, error),
             }
         }
-        Err(err) => println!("Failed to read file : {}", err),
     }
-    println!("{:#?}", error_logs);
-}
-
-// Returns Vec<String> - a collection of owned strings that live on the heap
-// This allows the error lines to outlive the original text and be passed around freely
-fn extract_errors(text: &str) -> Vec<String> {
-    let splt_text = text.split("\n");
-
-    let mut results: Vec<String> = vec![];
-    for line in splt_text {
-        if line.starts_with("ERROR") {
-            results.push(line.to_string());
+    if let Some(window) = window {
+        for record in window.into_records() {
+            found = true;
+            writer.write(&record)?;
         }
     }
-
-    results
+    writer.finish()?;
+    Ok(found)
 }
 
-fn basic_error_handling() -> () {
-    // Pattern 1: Match with both value and error
-    // Use when you need to access the successful value
-    match divide(5.0, 0.0) {
-        Ok(value) => println!("{}", value),
-        Err(err) => println!("{}", err),
-    }
-
-    // Pattern 2: Match with void value (using ..)
-    // Use when you only care about success/failure, not the actual value
-    // The .. pattern ignores the content when we don't need it
-    match validate_email(String::from("asdf@asdf.com")) {
-        Ok(..) => println!("email is valid"),
-        Err(err) => println!("{}", err),
+fn open_input(input: &Input) -> Result<Box<dyn BufRead>, Error> {
+    match input {
+        Input::Stdin => Ok(Box::new(io::stdin().lock())),
+        Input::File(path) => {
+            let file = File::open(path).map_err(|err| with_path(path, err))?;
+            Ok(Box::new(BufReader::new(file)))
+        }
     }
 }
 
-fn validate_email(email: String) -> Result<(), Error> {
-    if email.contains("@") {
-        Ok(()) // returning an empty Tuple
-    } else {
-        Err(Error::other("email must have a @"))
+// Creating the output truncates it, so it mustn't be one of the inputs. Only
+// an existing file can be one, and canonical paths see through "./" and
+// symlinks.
+fn refuse_input_as_output(output: &Path, inputs: &[Input]) -> Result<(), Error> {
+    let Ok(output) = output.canonicalize() else {
+        return Ok(());
+    };
+    for input in inputs {
+        if let Input::File(path) = input
+            && path.canonicalize().is_ok_and(|path| path == output)
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("{}: is also an input, not overwriting it", path.display()),
+            ));
+        }
     }
+    Ok(())
 }
 
-fn divide(a: f64, b: f64) -> Result<f64, Error> {
-    if b == 0.0 {
-        Err(Error::other("can't divide by 0"))
-    } else {
-        Ok(a / b)
-    }
+// io errors don't say which file they are about
+fn with_path(path: &Path, err: Error) -> Error {
+    Error::new(err.kind(), format!("{}: {}", path.display(), err))
 }
@@ -0,0 +1,182 @@
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use crate::entry::Record;
+use crate::search::{Search, highlight};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    // Each entry as a log line, rebuilt from its fields
+    Raw,
+    // One array of objects
+    Json,
+    // One object per line
+    Ndjson,
+    // A header row then one row per entry
+    Csv,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name.to_ascii_lowercase().as_str() {
+            "raw" => Some(Format::Raw),
+            "json" => Some(Format::Json),
+            "ndjson" | "jsonl" => Some(Format::Ndjson),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+const CSV_HEADER: &str = "level,time,message,source,line";
+
+// Writes records one at a time in the chosen format. The JSON array is opened
+// on the first record and closed by `finish`, so it streams like the others.
+pub struct RecordWriter<'a, W: Write> {
+    out: W,
+    format: Format,
+    // Names of the inputs, indexed by `Record::source`
+    sources: &'a [String],
+    // Raw output only: matches to color
+    highlight: Option<&'a Search>,
+    written: usize,
+}
+
+impl<'a, W: Write> RecordWriter<'a, W> {
+    pub fn new(
+        out: W,
+        format: Format,
+        sources: &'a [String],
+        highlight: Option<&'a Search>,
+    ) -> Self {
+        RecordWriter {
+            out,
+            format,
+            sources,
+            highlight,
+            written: 0,
+        }
+    }
+
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        let entry = &record.entry;
+        match self.format {
+            Format::Raw => match self.highlight {
+                Some(search) if !entry.message.is_empty() => {
+                    let spans = search.spans(&entry.message);
+                    let message = highlight(&entry.message, &spans);
+                    writeln!(self.out, "{} {} {}", entry.level, entry.time, message)?;
+                }
+                _ => writeln!(self.out, "{}", entry)?,
+            },
+            Format::Json => {
+                let open = if self.written == 0 { "[\n" } else { ",\n" };
+                let object = json_object(record, self.source(record));
+                write!(self.out, "{}  {}", open, object)?;
+            }
+            Format::Ndjson => {
+                let object = json_object(record, self.source(record));
+                writeln!(self.out, "{}", object)?;
+            }
+            Format::Csv => {
+                if self.written == 0 {
+                    writeln!(self.out, "{}", CSV_HEADER)?;
+                }
+                writeln!(
+                    self.out,
+                    "{},{},{},{},{}",
+                    entry.level,
+                    entry.time,
+                    csv_field(&entry.message),
+                    csv_field(self.source(record)),
+                    record.line
+                )?;
+            }
+        }
+        self.written += 1;
+        Ok(())
+    }
+
+    fn source(&self, record: &Record) -> &'a str {
+        &self.sources[record.source]
+    }
+
+    // Closes the JSON array and flushes. Without any records JSON gives `[]`
+    // and CSV still gets its header, so the output is never empty.
+    pub fn finish(mut self) -> io::Result<()> {
+        match self.format {
+            Format::Json if self.written == 0 => writeln!(self.out, "[]")?,
+            Format::Json => writeln!(self.out, "\n]")?,
+            Format::Csv if self.written == 0 => writeln!(self.out, "{}", CSV_HEADER)?,
+            _ => {}
+        }
+        self.out.flush()
+    }
+}
+
+// {"level":"ERROR","time":"14:33:45","message":"...","source":"app.log","line":3}
+fn json_object(record: &Record, source: &str) -> String {
+    let entry = &record.entry;
+    format!(
+        "{{\"level\":\"{}\",\"time\":\"{}\",\"message\":{},\"source\":{},\"line\":{}}}",
+        entry.level,
+        entry.time,
+        json_string(&entry.message),
+        json_string(source),
+        record.line
+    )
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", ch as u32);
+            }
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// Quoted when it holds a comma, quote or line break, quotes doubled (RFC 4180)
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_string_escapes_quotes_backslashes_and_control_characters() {
+        assert_eq!(json_string("plain"), r#""plain""#);
+        assert_eq!(json_string(r#"say "hi" \o/"#), r#""say \"hi\" \\o/""#);
+        assert_eq!(json_string("a\nb\r\tc"), r#""a\nb\r\tc""#);
+        assert_eq!(json_string("bell\u{7}"), r#""bell\u0007""#);
+        // Only control characters are escaped, the rest stays UTF-8
+        assert_eq!(json_string("café ✓"), "\"café ✓\"");
+    }
+
+    #[test]
+    fn csv_field_quotes_only_when_needed() {
+        assert_eq!(csv_field("disk full"), "disk full");
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+        assert_eq!(csv_field(r#"say "hi""#), r#""say ""hi""""#);
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field("cr\r"), "\"cr\r\"");
+        assert_eq!(csv_field(""), "");
+    }
+}
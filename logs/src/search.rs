@@ -0,0 +1,186 @@
+use std::ops::Range;
+
+use regex::Regex;
+
+const HIGHLIGHT: &str = "\x1b[1;31m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    Text(String),
+    Regex(Regex),
+}
+
+// Which messages to keep. An entry matches if any of the patterns is found in
+// its message, or none is with `invert`. No patterns at all keeps everything.
+#[derive(Debug, Clone, Default)]
+pub struct Search {
+    pub patterns: Vec<Pattern>,
+    // Applies to text patterns, regexes are compiled with it
+    pub ignore_case: bool,
+    pub invert: bool,
+}
+
+impl Search {
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    pub fn matches(&self, message: &str) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let found = self.patterns.iter().any(|pattern| match pattern {
+            Pattern::Text(needle) => find_text(message, needle, self.ignore_case, 0).is_some(),
+            Pattern::Regex(regex) => regex.is_match(message),
+        });
+        found != self.invert
+    }
+
+    // Byte ranges of every match in `message`, sorted, overlaps merged.
+    // Inverted searches have nothing to show.
+    pub fn spans(&self, message: &str) -> Vec<Range<usize>> {
+        if self.invert {
+            return vec![];
+        }
+        let mut spans: Vec<Range<usize>> = vec![];
+        for pattern in &self.patterns {
+            match pattern {
+                Pattern::Text(needle) => {
+                    let mut start = 0;
+                    while let Some(span) = find_text(message, needle, self.ignore_case, start) {
+                        start = match message[span.end..].chars().next() {
+                            // An empty needle would find the same spot again
+                            Some(ch) if span.is_empty() => span.end + ch.len_utf8(),
+                            None if span.is_empty() => message.len() + 1,
+                            _ => span.end,
+                        };
+                        spans.push(span);
+                        if start > message.len() {
+                            break;
+                        }
+                    }
+                }
+                Pattern::Regex(regex) => {
+                    spans.extend(regex.find_iter(message).map(|found| found.range()))
+                }
+            }
+        }
+        spans.retain(|span| !span.is_empty());
+        spans.sort_by_key(|span| span.start);
+
+        let mut merged: Vec<Range<usize>> = vec![];
+        for span in spans {
+            match merged.last_mut() {
+                Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+                _ => merged.push(span),
+            }
+        }
+        merged
+    }
+}
+
+// The first `needle` in `haystack` at or after byte `start`. Case is compared
+// char by char so the range is right even where lowercasing changes lengths.
+fn find_text(
+    haystack: &str,
+    needle: &str,
+    ignore_case: bool,
+    start: usize,
+) -> Option<Range<usize>> {
+    if !ignore_case {
+        return haystack[start..]
+            .find(needle)
+            .map(|i| start + i..start + i + needle.len());
+    }
+    let same = |a: char, b: char| a == b || a.to_lowercase().eq(b.to_lowercase());
+    haystack[start..].char_indices().find_map(|(i, _)| {
+        let begin = start + i;
+        let mut end = begin;
+        let mut rest = haystack[begin..].chars();
+        for want in needle.chars() {
+            let ch = rest.next().filter(|ch| same(*ch, want))?;
+            end += ch.len_utf8();
+        }
+        Some(begin..end)
+    })
+}
+
+// `message` with the `spans` wrapped in ANSI bold red
+pub fn highlight(message: &str, spans: &[Range<usize>]) -> String {
+    let mut out = String::with_capacity(message.len() + spans.len() * 11);
+    let mut last = 0;
+    for span in spans {
+        out.push_str(&message[last..span.start]);
+        out.push_str(HIGHLIGHT);
+        out.push_str(&message[span.clone()]);
+        out.push_str(RESET);
+        last = span.end;
+    }
+    out.push_str(&message[last..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search(patterns: Vec<Pattern>, ignore_case: bool) -> Search {
+        Search {
+            patterns,
+            ignore_case,
+            invert: false,
+        }
+    }
+
+    fn text(needle: &str) -> Pattern {
+        Pattern::Text(needle.to_string())
+    }
+
+    #[test]
+    fn overlapping_and_touching_spans_merge() {
+        let search = search(vec![text("disk"), text("k f"), text("full")], false);
+        assert_eq!(search.spans("disk full"), vec![0..9]);
+
+        let search = search_regex(&["a+", "b"]);
+        assert_eq!(search.spans("aab b aa"), vec![0..3, 4..5, 6..8]);
+    }
+
+    #[test]
+    fn spans_are_sorted_across_patterns() {
+        let search = search(vec![text("later"), text("first")], false);
+        assert_eq!(
+            search.spans("first, later, first"),
+            vec![0..5, 7..12, 14..19]
+        );
+    }
+
+    #[test]
+    fn empty_matches_and_inverted_searches_have_no_spans() {
+        assert!(search(vec![text("")], false).spans("abc").is_empty());
+        assert!(search_regex(&["x*"]).spans("abc").is_empty());
+
+        let mut inverted = search(vec![text("b")], false);
+        inverted.invert = true;
+        assert!(inverted.spans("abc").is_empty());
+    }
+
+    #[test]
+    fn find_text_ignoring_case_returns_byte_ranges_of_the_haystack() {
+        assert_eq!(find_text("Disk FULL", "full", true, 0), Some(5..9));
+        assert_eq!(find_text("Disk FULL", "full", false, 0), None);
+        assert_eq!(find_text("full, Full", "FULL", true, 1), Some(6..10));
+        // The Kelvin sign is three bytes and lowercases to a one-byte "k", the
+        // range still covers it
+        assert_eq!(find_text("5 \u{212A}elvin", "kelvin", true, 0), Some(2..10));
+        assert_eq!(find_text("ÉTÉ été", "été", true, 0), Some(0..5));
+    }
+
+    fn search_regex(patterns: &[&str]) -> Search {
+        let patterns = patterns
+            .iter()
+            .map(|pattern| Pattern::Regex(Regex::new(pattern).unwrap()))
+            .collect();
+        search(patterns, false)
+    }
+}
@@ -0,0 +1,102 @@
+use std::io::{self, BufRead};
+
+use crate::entry::{LineError, Record};
+
+// Reads log entries one line at a time, so memory use doesn't grow with the
+// size of the file. Only the current line is kept, in a buffer that is reused.
+//
+// Lines may end in "\n" or "\r\n". Bytes that aren't valid UTF-8 are replaced
+// with U+FFFD instead of failing, so a corrupt line only garbles its message.
+pub struct LogReader<R> {
+    reader: R,
+    // Index of the input, copied into every record
+    source: usize,
+    buf: Vec<u8>,
+    line: usize,
+    failed: bool,
+}
+
+impl<R: BufRead> LogReader<R> {
+    pub fn new(reader: R, source: usize) -> Self {
+        LogReader {
+            reader,
+            source,
+            buf: vec![],
+            line: 0,
+            failed: false,
+        }
+    }
+}
+
+// The outer Result is a read failure, which ends the iteration. The inner one
+// is a line that isn't a valid entry; reading carries on after it.
+impl<R: BufRead> Iterator for LogReader<R> {
+    type Item = io::Result<Result<Record, LineError>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        loop {
+            self.buf.clear();
+            match self.reader.read_until(b'\n', &mut self.buf) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    self.failed = true;
+                    return Some(Err(err));
+                }
+            }
+            self.line += 1;
+
+            let mut bytes = self.buf.as_slice();
+            if let Some(rest) = bytes.strip_suffix(b"\n") {
+                bytes = rest;
+            }
+            if let Some(rest) = bytes.strip_suffix(b"\r") {
+                bytes = rest;
+            }
+            let text = String::from_utf8_lossy(bytes);
+            // Blank lines are skipped, any other line becomes an entry or an error
+            if text.trim().is_empty() {
+                continue;
+            }
+            let line = self.line;
+            let parsed = match text.parse() {
+                Ok(entry) => Ok(Record {
+                    source: self.source,
+                    line,
+                    entry,
+                }),
+                Err(error) => Err(LineError { line, error }),
+            };
+            return Some(Ok(parsed));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn cleans_up_lines_and_keeps_their_numbers() {
+        let input: &[u8] = b"INFO 10:00:00 a\r\n\nWARNING 10:00:01 caf\xff\r\n  \r\nnot a log line\nERROR 10:00:02 last";
+        let items: Vec<_> = LogReader::new(Cursor::new(input), 2)
+            .map(|item| item.unwrap())
+            .collect();
+        assert_eq!(items.len(), 4);
+
+        let record = |i: usize| items[i].as_ref().unwrap();
+        assert_eq!((record(0).line, record(0).source), (1, 2));
+        assert_eq!(record(0).entry.message, "a");
+        assert_eq!(record(1).line, 3);
+        assert_eq!(record(1).entry.message, "caf\u{FFFD}");
+        assert_eq!(items[2].as_ref().unwrap_err().line, 5);
+        // The last line doesn't need a newline
+        assert_eq!(record(3).line, 6);
+        assert_eq!(record(3).entry.message, "last");
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::VecDeque;
+
+use crate::entry::{Record, Time};
+
+// Entries between two times of day, both included. Either end can be left
+// open. Log times have no date, so a range whose start is after its end
+// (22:00 to 02:00) is taken to cross midnight.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeRange {
+    pub from: Option<Time>,
+    pub to: Option<Time>,
+}
+
+impl TimeRange {
+    pub fn contains(&self, time: Time) -> bool {
+        match (self.from, self.to) {
+            (Some(from), Some(to)) if from > to => time >= from || time <= to,
+            (from, to) => from.is_none_or(|from| time >= from) && to.is_none_or(|to| time <= to),
+        }
+    }
+}
+
+// "14:40" or "14:40:30". Without seconds, the end of a range is the end of
+// that minute, so `--until 14:50` keeps 14:50:59.
+pub fn parse_clock(text: &str, end_of_range: bool) -> Option<Time> {
+    let parts: Vec<&str> = text.split(':').collect();
+    let number = |part: &str| {
+        if part.len() == 2 && part.bytes().all(|b| b.is_ascii_digit()) {
+            part.parse().ok()
+        } else {
+            None
+        }
+    };
+    match parts.as_slice() {
+        [hour, minute] => {
+            let second = if end_of_range { 59 } else { 0 };
+            Time::new(number(hour)?, number(minute)?, second)
+        }
+        [hour, minute, second] => Time::new(number(hour)?, number(minute)?, number(second)?),
+        _ => None,
+    }
+}
+
+// "90s", "15m", "2h", or a bare number of minutes. Returns seconds.
+pub fn parse_duration(text: &str) -> Option<u32> {
+    let (digits, unit) = match text.char_indices().find(|(_, ch)| !ch.is_ascii_digit()) {
+        Some((i, _)) => text.split_at(i),
+        None => (text, "m"),
+    };
+    let amount: u32 = digits.parse().ok()?;
+    let unit = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        _ => return None,
+    };
+    amount.checked_mul(unit)
+}
+
+// The entries from the last `seconds` of a log, counted back from its newest
+// entry. That entry is only known at the end, so candidates are held until
+// then, but anything already too old for the newest time seen so far is
+// dropped: memory grows with the size of the window, not of the log.
+#[derive(Debug, Clone)]
+pub struct LastWindow {
+    seconds: u32,
+    newest: Option<Time>,
+    records: VecDeque<Record>,
+}
+
+impl LastWindow {
+    pub fn new(seconds: u32) -> Self {
+        LastWindow {
+            seconds,
+            newest: None,
+            records: VecDeque::new(),
+        }
+    }
+
+    // Every entry of the log counts towards the newest time, not only the ones
+    // that are kept
+    pub fn see(&mut self, time: Time) {
+        if self.newest.is_some_and(|newest| newest >= time) {
+            return;
+        }
+        self.newest = Some(time);
+        // Logs are mostly in order, so the old entries are at the front
+        while self
+            .records
+            .front()
+            .is_some_and(|record| !self.in_window(record.entry.time))
+        {
+            self.records.pop_front();
+        }
+    }
+
+    pub fn push(&mut self, record: Record) {
+        self.see(record.entry.time);
+        if self.in_window(record.entry.time) {
+            self.records.push_back(record);
+        }
+    }
+
+    // The kept records in the order they were pushed
+    pub fn into_records(self) -> impl Iterator<Item = Record> {
+        let cutoff = self.cutoff();
+        self.records
+            .into_iter()
+            .filter(move |record| record.entry.time.seconds_since_midnight() >= cutoff)
+    }
+
+    fn in_window(&self, time: Time) -> bool {
+        time.seconds_since_midnight() >= self.cutoff()
+    }
+
+    // Windows don't reach back past midnight
+    fn cutoff(&self) -> u32 {
+        self.newest.map_or(0, |newest| {
+            newest.seconds_since_midnight().saturating_sub(self.seconds)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(text: &str) -> Time {
+        text.parse().unwrap()
+    }
+
+    fn record(line: usize, time: &str) -> Record {
+        Record {
+            source: 0,
+            line,
+            entry: format!("INFO {} line {}", time, line).parse().unwrap(),
+        }
+    }
+
+    fn lines(window: LastWindow) -> Vec<usize> {
+        window.into_records().map(|record| record.line).collect()
+    }
+
+    #[test]
+    fn ranges_can_wrap_past_midnight() {
+        let night = TimeRange {
+            from: Some(time("22:00:00")),
+            to: Some(time("02:00:00")),
+        };
+        assert!(night.contains(time("23:30:00")));
+        assert!(night.contains(time("00:00:00")));
+        assert!(night.contains(time("02:00:00")));
+        assert!(!night.contains(time("12:00:00")));
+        assert!(!night.contains(time("21:59:59")));
+
+        let open = TimeRange {
+            from: None,
+            to: Some(time("02:00:00")),
+        };
+        assert!(open.contains(time("00:00:00")));
+        assert!(!open.contains(time("23:00:00")));
+    }
+
+    #[test]
+    fn clock_without_seconds_ends_at_the_end_of_the_minute() {
+        assert_eq!(parse_clock("14:50", false), Some(time("14:50:00")));
+        assert_eq!(parse_clock("14:50", true), Some(time("14:50:59")));
+        assert_eq!(parse_clock("14:50:10", true), Some(time("14:50:10")));
+        assert_eq!(parse_clock("7:5", false), None);
+        assert_eq!(parse_clock("24:00", false), None);
+        assert_eq!(parse_clock("14", false), None);
+    }
+
+    #[test]
+    fn durations_default_to_minutes() {
+        assert_eq!(parse_duration("90s"), Some(90));
+        assert_eq!(parse_duration("15"), Some(15 * 60));
+        assert_eq!(parse_duration("2h"), Some(2 * 3600));
+        assert_eq!(parse_duration("5x"), None);
+        assert_eq!(parse_duration("h"), None);
+        // Too many seconds for a u32
+        assert_eq!(parse_duration("2000000h"), None);
+    }
+
+    #[test]
+    fn last_window_counts_back_from_the_newest_entry() {
+        let mut window = LastWindow::new(60);
+        window.push(record(1, "10:00:00"));
+        window.push(record(2, "10:01:30"));
+        // Out of order: already too old, then still recent enough
+        window.push(record(3, "10:00:20"));
+        window.push(record(4, "10:00:45"));
+        assert_eq!(lines(window.clone()), [2, 4]);
+
+        // A newer entry that was filtered out still moves the window
+        window.see(time("10:01:50"));
+        assert_eq!(lines(window), [2]);
+    }
+
+    #[test]
+    fn last_window_stops_at_midnight() {
+        let mut window = LastWindow::new(600);
+        window.push(record(1, "00:00:10"));
+        window.push(record(2, "00:05:00"));
+        assert_eq!(lines(window), [1, 2]);
+    }
+}